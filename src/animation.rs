@@ -0,0 +1,286 @@
+//! Sprite-sheet animation for the player, driven by its movement controller.
+//!
+//! The animation state is picked each frame from the controller's velocity,
+//! intent and [`MotionRegime`], and playback speeds up as the player does.
+
+use std::time::Duration;
+
+use bevy::{
+    image::{ImageLoaderSettings, ImageSampler},
+    prelude::*,
+    sprite::Anchor,
+};
+
+use crate::{
+    AppSystems, MotionParameters,
+    asset_tracking::LoadResource,
+    player::{Controller, MotionRegime, MovementIntent},
+    side_scroll::movement::BasicMovementController,
+    top_down::movement::MovementController,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PlayerAnimation>();
+    app.register_type::<PlayerAssets>();
+    app.load_resource::<PlayerAssets>();
+
+    app.add_systems(
+        Update,
+        (
+            update_animation_timer.in_set(AppSystems::TickTimers),
+            (
+                update_animation_state::<BasicMovementController>,
+                update_animation_state::<MovementController>,
+                update_animation_atlas,
+            )
+                .chain()
+                .in_set(AppSystems::Update),
+        ),
+    );
+}
+
+/// Number of frames in each row of a player sprite sheet.
+const SHEET_COLUMNS: u32 = 6;
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerAssets {
+    #[dependency]
+    side_scroll: Handle<Image>,
+    side_scroll_layout: Handle<TextureAtlasLayout>,
+    #[dependency]
+    top_down: Handle<Image>,
+    top_down_layout: Handle<TextureAtlasLayout>,
+}
+
+impl FromWorld for PlayerAssets {
+    fn from_world(world: &mut World) -> Self {
+        let rows = PlayerAnimationState::ALL.len() as u32;
+        let mut layouts = world.resource_mut::<Assets<TextureAtlasLayout>>();
+        let side_scroll_layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::new(32, 48),
+            SHEET_COLUMNS,
+            rows,
+            None,
+            None,
+        ));
+        let top_down_layout = layouts.add(TextureAtlasLayout::from_grid(
+            UVec2::splat(32),
+            SHEET_COLUMNS,
+            rows,
+            None,
+            None,
+        ));
+
+        let assets = world.resource::<AssetServer>();
+        let pixel_art = |settings: &mut ImageLoaderSettings| {
+            // Use `nearest` image sampling to preserve pixel art style.
+            settings.sampler = ImageSampler::nearest();
+        };
+        Self {
+            side_scroll: assets.load_with_settings("images/player_side.png", pixel_art),
+            side_scroll_layout,
+            top_down: assets.load_with_settings("images/player_top.png", pixel_art),
+            top_down_layout,
+        }
+    }
+}
+
+/// An animated sprite for a side-scroll player, standing on its parent's position.
+pub fn side_scroll_sprite(assets: &PlayerAssets) -> impl Bundle {
+    (
+        Name::new("Player Sprite"),
+        Sprite {
+            anchor: Anchor::BottomCenter,
+            ..Sprite::from_atlas_image(
+                assets.side_scroll.clone(),
+                TextureAtlas::from(assets.side_scroll_layout.clone()),
+            )
+        },
+        PlayerAnimation::new(Facing::Flip),
+    )
+}
+
+/// An animated sprite for a top-down player, centred on its parent's position.
+pub fn top_down_sprite(assets: &PlayerAssets) -> impl Bundle {
+    (
+        Name::new("Player Sprite"),
+        Sprite {
+            custom_size: Some(Vec2::splat(24.0)),
+            ..Sprite::from_atlas_image(
+                assets.top_down.clone(),
+                TextureAtlas::from(assets.top_down_layout.clone()),
+            )
+        },
+        PlayerAnimation::new(Facing::Rotate),
+    )
+}
+
+/// Animation state of a player sprite. Lives on a child of the entity with
+/// the movement controller.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PlayerAnimation {
+    timer: Timer,
+    frame: usize,
+    state: PlayerAnimationState,
+    /// Playback speed multiplier, scaled with the controller's speed.
+    rate: f32,
+    facing: Facing,
+}
+
+#[derive(Reflect, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum PlayerAnimationState {
+    Idle,
+    Walk,
+    Run,
+    /// Moving against the intent, in the `alpha_rev` regime.
+    Skid,
+    /// Coasting to a halt with no intent, in the `alpha_stop` regime.
+    Stop,
+}
+
+/// How a sprite shows which way its controller is moving.
+#[derive(Reflect, PartialEq, Eq, Clone, Copy, Debug)]
+enum Facing {
+    /// Mirror the sprite when moving left.
+    Flip,
+    /// Turn the sprite to face along the velocity.
+    Rotate,
+}
+
+impl PlayerAnimationState {
+    const ALL: [Self; 5] = [Self::Idle, Self::Walk, Self::Run, Self::Skid, Self::Stop];
+
+    fn row(self) -> usize {
+        self as usize
+    }
+
+    fn frames(self) -> usize {
+        match self {
+            Self::Idle => 4,
+            Self::Walk | Self::Run => 6,
+            Self::Skid | Self::Stop => 2,
+        }
+    }
+
+    fn interval(self) -> Duration {
+        Duration::from_millis(match self {
+            Self::Idle => 250,
+            Self::Walk => 120,
+            Self::Run => 80,
+            Self::Skid => 100,
+            Self::Stop => 150,
+        })
+    }
+
+    /// Pick a state and playback rate from the controller's state.
+    fn select(speed: f32, max_speed: f32, intent: Vec2, regime: MotionRegime) -> (Self, f32) {
+        let fraction = speed / max_speed;
+        match regime {
+            MotionRegime::Reversing if fraction > 0.1 => (Self::Skid, 1.0),
+            MotionRegime::Stopping if fraction > 0.15 => (Self::Stop, 1.0),
+            _ if fraction < 0.05 && intent == Vec2::ZERO => (Self::Idle, 1.0),
+            _ if fraction < 0.55 => (Self::Walk, (fraction / 0.3).clamp(0.25, 2.0)),
+            _ => (Self::Run, (fraction / 0.8).clamp(0.5, 2.0)),
+        }
+    }
+}
+
+impl PlayerAnimation {
+    fn new(facing: Facing) -> Self {
+        let state = PlayerAnimationState::Idle;
+        Self {
+            timer: Timer::new(state.interval(), TimerMode::Repeating),
+            frame: 0,
+            state,
+            rate: 1.0,
+            facing,
+        }
+    }
+
+    fn update_timer(&mut self, delta: Duration) {
+        self.timer.tick(delta.mul_f32(self.rate));
+        if !self.timer.finished() {
+            return;
+        }
+        let frames = self.state.frames();
+        self.frame = (self.frame + self.timer.times_finished_this_tick() as usize) % frames;
+    }
+
+    fn update_state(&mut self, state: PlayerAnimationState, rate: f32) {
+        self.rate = rate;
+        if self.state != state {
+            self.state = state;
+            self.frame = 0;
+            self.timer = Timer::new(state.interval(), TimerMode::Repeating);
+        }
+    }
+
+    fn atlas_index(&self) -> usize {
+        self.state.row() * SHEET_COLUMNS as usize + self.frame
+    }
+
+    pub fn state(&self) -> PlayerAnimationState {
+        self.state
+    }
+}
+
+fn update_animation_timer(time: Res<Time>, mut query: Query<&mut PlayerAnimation>) {
+    for mut animation in &mut query {
+        animation.update_timer(time.delta());
+    }
+}
+
+fn update_animation_state<C: Controller>(
+    params: Res<MotionParameters>,
+    controllers: Query<(&C, &MovementIntent, &MotionRegime)>,
+    mut sprites: Query<(&ChildOf, &mut PlayerAnimation, &mut Sprite, &mut Transform)>,
+) {
+    for (child_of, mut animation, mut sprite, mut transform) in &mut sprites {
+        let Ok((controller, intent, regime)) = controllers.get(child_of.parent()) else {
+            continue;
+        };
+        let velocity = controller.planar_velocity();
+        let (state, rate) =
+            PlayerAnimationState::select(velocity.length(), params.max_speed, intent.0, *regime);
+        animation.update_state(state, rate);
+
+        match animation.facing {
+            Facing::Flip => {
+                if velocity.x != 0.0 {
+                    // Skidding faces the way we're trying to go, not the way we're sliding.
+                    let leftwards = if state == PlayerAnimationState::Skid {
+                        intent.0.x < 0.0
+                    } else {
+                        velocity.x < 0.0
+                    };
+                    if sprite.flip_x != leftwards {
+                        sprite.flip_x = leftwards;
+                    }
+                }
+            }
+            Facing::Rotate => {
+                let heading = if state == PlayerAnimationState::Skid {
+                    intent.0
+                } else {
+                    velocity
+                };
+                if let Some(heading) = heading.try_normalize() {
+                    transform.rotation = Quat::from_rotation_z(heading.to_angle());
+                }
+            }
+        }
+    }
+}
+
+fn update_animation_atlas(mut query: Query<(&PlayerAnimation, &mut Sprite)>) {
+    for (animation, mut sprite) in &mut query {
+        let index = animation.atlas_index();
+        if let Some(atlas) = sprite.texture_atlas.as_mut()
+            && atlas.index != index
+        {
+            atlas.index = index;
+        }
+    }
+}
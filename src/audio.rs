@@ -0,0 +1,84 @@
+//! Sound effect helpers and the global volume setting.
+
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::input::{Action, action_just_pressed};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SoundEffect>();
+    app.register_type::<AudioSettings>();
+    app.init_resource::<AudioSettings>();
+
+    app.add_systems(
+        Update,
+        (
+            toggle_mute.run_if(action_just_pressed(Action::ToggleMute)),
+            (apply_audio_settings, apply_global_volume)
+                .chain()
+                .run_if(resource_changed::<AudioSettings>),
+        )
+            .chain(),
+    );
+}
+
+/// An organizational marker component that should be added to a spawned [`AudioPlayer`] if it's in the
+/// general "sound effect" category (e.g. footsteps, the sound of a skid).
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct SoundEffect;
+
+/// A sound effect audio instance, played once at `volume` relative to the global volume.
+pub fn sound_effect(handle: Handle<AudioSource>, volume: f32) -> impl Bundle {
+    (
+        AudioPlayer(handle),
+        PlaybackSettings::DESPAWN.with_volume(Volume::Linear(volume)),
+        SoundEffect,
+    )
+}
+
+/// User-facing audio settings, applied to [`GlobalVolume`] whenever they change.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct AudioSettings {
+    /// Linear volume from 0 to 1.
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            volume: 0.5,
+            muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn effective_volume(&self) -> Volume {
+        if self.muted {
+            Volume::SILENT
+        } else {
+            Volume::Linear(self.volume.clamp(0.0, 1.0))
+        }
+    }
+}
+
+fn toggle_mute(mut settings: ResMut<AudioSettings>) {
+    settings.muted = !settings.muted;
+}
+
+fn apply_audio_settings(settings: Res<AudioSettings>, mut global_volume: ResMut<GlobalVolume>) {
+    global_volume.volume = settings.effective_volume();
+}
+
+/// [`GlobalVolume`] doesn't apply to already-running audio entities, so this system will update them.
+fn apply_global_volume(
+    global_volume: Res<GlobalVolume>,
+    mut audio_query: Query<(&PlaybackSettings, &mut AudioSink)>,
+) {
+    for (playback, mut sink) in &mut audio_query {
+        sink.set_volume(global_volume.volume * playback.volume);
+    }
+}
@@ -0,0 +1,10 @@
+// Support configuring Bevy lints within code.
+#![cfg_attr(bevy_lint, feature(register_tool), register_tool(bevy))]
+
+use bevy::prelude::*;
+use char_motion_example::cli::{Cli, LaunchError};
+use clap::Parser;
+
+pub fn main() -> Result<AppExit, LaunchError> {
+    Cli::parse().run()
+}
@@ -0,0 +1,536 @@
+//! The command-line interface of the native `char_motion` binary.
+//!
+//! Options given on the command line take priority over saved settings, and
+//! individual tuning values take priority over `--preset`.
+
+use std::{fs, path::PathBuf, time::Duration};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use clap::{Parser, Subcommand};
+
+use crate::{
+    AppPlugin, AppSystems, CharMotionPlugin, MotionParameters, PlayMode,
+    player::{MovementIntent, Player},
+    presets::{self, RequestedPreset, SelectedPreset},
+    replay::{InputRecorder, InputRecording, InputReplay},
+    screens::Screen,
+    side_scroll,
+    sweep::{self, SweepValues},
+    top_down,
+    trace::MotionTrace,
+};
+
+/// Experiment with character motion tunings. Starts on the title screen when
+/// no command is given.
+#[derive(Parser, Debug)]
+#[command(name = "char_motion", version)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[command(flatten)]
+    Play(Game),
+    /// Run a game without a window and print where each player ends up.
+    /// The player is driven by `--intent`, or by a recording given with the
+    /// game's `--replay`.
+    Simulate {
+        #[command(flatten)]
+        script: ScriptArgs,
+        #[command(subcommand)]
+        game: Game,
+    },
+    /// Watch a recording made with `--record`.
+    Replay {
+        file: PathBuf,
+        #[command(subcommand)]
+        game: Game,
+    },
+    /// Run a game without a window and write each player's motion on every
+    /// frame as CSV. The player is driven by `--intent`, or by a recording
+    /// given with the game's `--replay`.
+    Export {
+        /// Where to write the CSV, instead of standard output.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        script: ScriptArgs,
+        #[command(subcommand)]
+        game: Game,
+    },
+    /// Measure a standard set of manoeuvres with both controllers for every
+    /// combination of the given values, and write the metrics as CSV. Each
+    /// value is a number, a comma separated list or `start:end:count`.
+    Sweep {
+        #[arg(long, default_value = "500")]
+        max_speed: SweepValues,
+        #[arg(long, default_value = "1")]
+        t_acc: SweepValues,
+        #[arg(long, default_value = "1")]
+        alpha_stop: SweepValues,
+        #[arg(long, default_value = "1")]
+        alpha_rev: SweepValues,
+        #[arg(long, default_value = "1")]
+        alpha_turn: SweepValues,
+        /// Where to write the CSV, instead of standard output.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum Game {
+    /// The side-scroll mode.
+    SideScroll {
+        /// The ground to start on, instead of the last one used.
+        #[arg(long, value_enum)]
+        ground: Option<side_scroll::Ground>,
+        /// The seed the endless ground is generated from.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// The steepest the endless ground gets, as rise over run.
+        #[arg(long, default_value_t = 0.5)]
+        steepness: f32,
+        /// How many bumps there are in every 1000 pixels of endless ground.
+        #[arg(long, default_value_t = 2.0)]
+        bump_frequency: f32,
+        #[command(flatten)]
+        launch: LaunchArgs,
+    },
+    /// The top-down mode.
+    TopDown {
+        /// The ground to start on, instead of the last one used.
+        #[arg(long, value_enum)]
+        ground: Option<top_down::Ground>,
+        #[command(flatten)]
+        launch: LaunchArgs,
+    },
+}
+
+impl Cli {
+    /// Run the chosen command to completion.
+    pub fn run(self) -> Result<AppExit, LaunchError> {
+        let Some(command) = self.command else {
+            return Ok(crate::run_menu());
+        };
+        match command {
+            Command::Play(game) => Ok(game.app()?.run()),
+            Command::Simulate { script, game } => {
+                Ok(game.headless_app(TraceOutput::Summary, script)?.run())
+            }
+            Command::Replay { file, mut game } => {
+                game.launch_mut().replay = Some(file);
+                Ok(game.app()?.run())
+            }
+            Command::Export {
+                output,
+                script,
+                game,
+            } => Ok(game.headless_app(TraceOutput::Csv(output), script)?.run()),
+            Command::Sweep {
+                max_speed,
+                t_acc,
+                alpha_stop,
+                alpha_rev,
+                alpha_turn,
+                output,
+            } => {
+                let rows = sweep::sweep(&max_speed, &t_acc, &alpha_stop, &alpha_rev, &alpha_turn);
+                Ok(write_csv(output.as_ref(), &sweep::to_csv(&rows)))
+            }
+        }
+    }
+}
+
+impl Game {
+    fn launch_mut(&mut self) -> &mut LaunchArgs {
+        match self {
+            Game::SideScroll { launch, .. } | Game::TopDown { launch, .. } => launch,
+        }
+    }
+
+    fn app(self) -> Result<App, LaunchError> {
+        match self {
+            Game::SideScroll {
+                ground,
+                seed,
+                steepness,
+                bump_frequency,
+                launch,
+            } => launch.side_scroll_app(
+                ground,
+                side_scroll::EndlessTerrain {
+                    seed,
+                    steepness,
+                    bump_frequency,
+                },
+            ),
+            Game::TopDown { ground, launch } => launch.top_down_app(ground),
+        }
+    }
+
+    /// An app that runs without a window, for one simulated minute unless
+    /// told otherwise, and reports the players' motion on exit.
+    fn headless_app(mut self, output: TraceOutput, script: ScriptArgs) -> Result<App, LaunchError> {
+        let launch = self.launch_mut();
+        launch.headless = true;
+        match (script.intent, launch.replay.is_some()) {
+            (None, false) => {
+                return Err(LaunchError(
+                    "Nothing would drive the player: give --intent or the game's --replay".into(),
+                ));
+            }
+            (Some(_), true) => {
+                return Err(LaunchError(
+                    "--intent and --replay both drive the player: give only one".into(),
+                ));
+            }
+            (Some(_), false) => {
+                launch.frames.get_or_insert(3600);
+            }
+            (None, true) => {}
+        }
+
+        let mut app = self.app()?;
+        if let Some(intent) = script.intent {
+            app.insert_resource(ScriptedIntent(intent));
+            app.add_systems(
+                Update,
+                hold_scripted_intent
+                    .after(AppSystems::RecordInput)
+                    .before(AppSystems::Update)
+                    .run_if(in_state(Screen::Gameplay)),
+            );
+        }
+        app.init_resource::<MotionTrace>();
+        app.insert_resource(output);
+        app.add_systems(Last, write_trace.run_if(on_event::<AppExit>));
+        Ok(app)
+    }
+}
+
+/// How to drive the player in a run without a window.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct ScriptArgs {
+    /// Hold this movement intent, given as `x,y`, for the whole run. Runs
+    /// for one simulated minute unless the game's `--frames` says otherwise.
+    #[arg(long, value_name = "X,Y", value_parser = parse_intent, allow_hyphen_values = true)]
+    pub intent: Option<Vec2>,
+}
+
+/// Parses an intent such as `1,0` or `-0.5,1`.
+fn parse_intent(text: &str) -> Result<Vec2, String> {
+    let (x, y) = text
+        .split_once(',')
+        .ok_or_else(|| format!("expected x,y but got {text:?}"))?;
+    let component = |value: &str| {
+        value
+            .trim()
+            .parse::<f32>()
+            .map_err(|error| format!("{value:?}: {error}"))
+    };
+    Ok(Vec2::new(component(x)?, component(y)?))
+}
+
+/// The intent held by every player in a scripted run.
+#[derive(Resource, Debug)]
+struct ScriptedIntent(Vec2);
+
+/// Replaces whatever intent input gave the players with the scripted one.
+fn hold_scripted_intent(
+    script: Res<ScriptedIntent>,
+    mut players: Query<&mut MovementIntent, With<Player>>,
+) {
+    for mut intent in &mut players {
+        intent.0 = script.0;
+    }
+}
+
+/// Options for launching a game, flattened into each binary's arguments.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct LaunchArgs {
+    /// Start from a named tuning in the presets file.
+    #[arg(long)]
+    pub preset: Option<String>,
+    /// The speed reached when holding a direction on flat ground.
+    #[arg(long)]
+    pub max_speed: Option<f32>,
+    /// The time scale, in seconds, over which `max_speed` is approached.
+    #[arg(long)]
+    pub t_acc: Option<f32>,
+    /// Damping applied when there is no intent.
+    #[arg(long)]
+    pub alpha_stop: Option<f32>,
+    /// Damping applied when moving against the intent.
+    #[arg(long)]
+    pub alpha_rev: Option<f32>,
+    /// Damping applied to velocity across the intent in top-down mode.
+    #[arg(long)]
+    pub alpha_turn: Option<f32>,
+    /// How strongly slopes speed up or slow down a side-scroll player.
+    #[arg(long)]
+    pub gravity_strength: Option<f32>,
+    /// Spawn this many NPCs around the player, which seek, patrol, flee and
+    /// wander in turn.
+    #[arg(long, default_value_t = 0)]
+    pub npcs: usize,
+    /// Window width, in logical pixels.
+    #[arg(long, default_value_t = 1280.0)]
+    pub width: f32,
+    /// Window height, in logical pixels.
+    #[arg(long, default_value_t = 720.0)]
+    pub height: f32,
+    /// Advance the simulation by this many seconds every frame, rather than
+    /// by the real frame time.
+    #[arg(long, value_name = "SECONDS")]
+    pub fixed_timestep: Option<f64>,
+    /// Run without a window or renderer, at 60 simulated frames per second
+    /// unless `--fixed-timestep` is given. Combine with `--frames` or
+    /// `--replay` so that the app exits.
+    #[arg(long)]
+    pub headless: bool,
+    /// Exit after this many frames.
+    #[arg(long)]
+    pub frames: Option<u32>,
+    /// Record the keys held on each gameplay frame to this file.
+    #[arg(long, value_name = "FILE")]
+    pub record: Option<PathBuf>,
+    /// Play back a file made with `--record` instead of reading the keyboard,
+    /// exiting when it ends. Uses the recorded fixed timestep unless
+    /// `--fixed-timestep` is given.
+    #[arg(long, value_name = "FILE", conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+}
+
+impl LaunchArgs {
+    fn side_scroll_app(
+        self,
+        ground: Option<side_scroll::Ground>,
+        terrain: side_scroll::EndlessTerrain,
+    ) -> Result<App, LaunchError> {
+        let mut app = self.app(
+            PlayMode::SideScroll,
+            MotionParameters::full(500.0, 1.0, 5.0, 5.0),
+        )?;
+        app.insert_resource(terrain);
+        if let Some(ground) = ground {
+            app.insert_resource(GroundOverride(ground));
+            app.add_systems(
+                OnExit(Screen::Loading),
+                apply_ground_override::<side_scroll::Ground>,
+            );
+        }
+        Ok(app)
+    }
+
+    fn top_down_app(self, ground: Option<top_down::Ground>) -> Result<App, LaunchError> {
+        let mut app = self.app(
+            PlayMode::TopDown,
+            MotionParameters::full_with_separate_turn(500.0, 1.0, 5.0, 5.0, 5.0),
+        )?;
+        if let Some(ground) = ground {
+            app.insert_resource(GroundOverride(ground));
+            app.add_systems(
+                OnExit(Screen::Loading),
+                apply_ground_override::<top_down::Ground>,
+            );
+        }
+        Ok(app)
+    }
+
+    fn app(self, mode: PlayMode, params: MotionParameters) -> Result<App, LaunchError> {
+        let replay = self
+            .replay
+            .as_ref()
+            .map(|path| {
+                InputRecording::load(path).map_err(|error| {
+                    LaunchError(format!("Couldn't read {}: {error}", path.display()))
+                })
+            })
+            .transpose()?;
+        // Headless frames run back to back, so their real duration means nothing.
+        let timestep = self
+            .fixed_timestep
+            .or_else(|| replay.as_ref().and_then(|replay| replay.timestep))
+            .or(self.headless.then_some(1.0 / 60.0));
+
+        let mut app = App::new();
+        app.add_plugins(AppPlugin {
+            window_size: Vec2::new(self.width, self.height),
+            headless: self.headless,
+            ..AppPlugin::new(
+                CharMotionPlugin::new()
+                    .mode(mode)
+                    .params(params)
+                    .npcs(self.npcs),
+            )
+        });
+
+        if let Some(timestep) = timestep {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                timestep,
+            )));
+        }
+        if let Some(frames) = self.frames {
+            app.add_systems(Update, exit_after(frames));
+        }
+        if let Some(path) = self.record.clone() {
+            app.insert_resource(InputRecorder {
+                path,
+                recording: InputRecording {
+                    timestep,
+                    start: None,
+                    frames: Vec::new(),
+                },
+            });
+        }
+        if let Some(recording) = replay {
+            app.insert_resource(InputReplay {
+                recording,
+                frame: 0,
+            });
+        }
+        if let Some(preset) = self.preset.clone() {
+            app.insert_resource(RequestedPreset(preset));
+        }
+        app.insert_resource(ParamOverrides(self));
+        app.add_systems(
+            OnExit(Screen::Loading),
+            apply_param_overrides.after(presets::apply_requested_preset),
+        );
+        Ok(app)
+    }
+}
+
+/// Why a game couldn't be launched. Its `Debug` output is just the message,
+/// as that's what `main` prints on returning it.
+pub struct LaunchError(String);
+
+impl std::fmt::Debug for LaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for LaunchError {}
+
+/// What to do with the [`MotionTrace`] of a headless run.
+#[derive(Resource, Debug)]
+enum TraceOutput {
+    /// Print the final position and velocity of each player.
+    Summary,
+    /// Write every sample as CSV, to a file or standard output.
+    Csv(Option<PathBuf>),
+}
+
+#[derive(Resource, Debug)]
+struct ParamOverrides(LaunchArgs);
+
+#[derive(Resource, Debug)]
+struct GroundOverride<G>(G);
+
+fn apply_param_overrides(
+    overrides: Res<ParamOverrides>,
+    mut params: ResMut<MotionParameters>,
+    mut selected: ResMut<SelectedPreset>,
+) {
+    let args = &overrides.0;
+    // Any override makes the tuning a custom one, even on top of a preset.
+    let overridden = [
+        args.max_speed,
+        args.t_acc,
+        args.alpha_stop,
+        args.alpha_rev,
+        args.alpha_turn,
+        args.gravity_strength,
+    ];
+    if overridden.iter().any(Option::is_some) {
+        selected.0 = None;
+    }
+    if let Some(max_speed) = args.max_speed {
+        params.set_max_speed(max_speed);
+    }
+    if let Some(t_acc) = args.t_acc {
+        params.set_t_acc(t_acc);
+    }
+    if let Some(alpha_stop) = args.alpha_stop {
+        params.set_alpha_stop(alpha_stop);
+    }
+    if let Some(alpha_rev) = args.alpha_rev {
+        params.set_alpha_rev(alpha_rev);
+    }
+    if let Some(alpha_turn) = args.alpha_turn {
+        params.set_alpha_turn(alpha_turn);
+    }
+    if let Some(gravity_strength) = args.gravity_strength {
+        params.set_gravity_strength(gravity_strength);
+    }
+}
+
+fn apply_ground_override<G: Resource + Copy>(
+    ground_override: Res<GroundOverride<G>>,
+    mut ground: ResMut<G>,
+) {
+    *ground = ground_override.0;
+}
+
+fn exit_after(frames: u32) -> impl FnMut(Res<bevy::diagnostic::FrameCount>, EventWriter<AppExit>) {
+    move |frame_count, mut exit| {
+        if frame_count.0 >= frames {
+            exit.write(AppExit::Success);
+        }
+    }
+}
+
+fn write_trace(trace: Res<MotionTrace>, output: Res<TraceOutput>) {
+    match &*output {
+        TraceOutput::Summary => {
+            let Some(last) = trace.samples.last() else {
+                println!("No gameplay frames were simulated");
+                return;
+            };
+            // Each frame records every player, so the last frame's samples share its number.
+            for sample in trace
+                .samples
+                .iter()
+                .filter(|sample| sample.frame == last.frame)
+            {
+                println!(
+                    "t={:.3}s position=({:.1}, {:.1}) velocity=({:.1}, {:.1}) regime={:?}",
+                    sample.time,
+                    sample.position.x,
+                    sample.position.y,
+                    sample.velocity.x,
+                    sample.velocity.y,
+                    sample.regime,
+                );
+            }
+        }
+        TraceOutput::Csv(path) => {
+            write_csv(path.as_ref(), &trace.to_csv());
+        }
+    }
+}
+
+/// Write `csv` to `path`, or to standard output without one.
+fn write_csv(path: Option<&PathBuf>, csv: &str) -> AppExit {
+    let Some(path) = path else {
+        print!("{csv}");
+        return AppExit::Success;
+    };
+    match fs::write(path, csv) {
+        Ok(()) => AppExit::Success,
+        Err(error) => {
+            eprintln!("Failed to write {}: {error}", path.display());
+            AppExit::error()
+        }
+    }
+}
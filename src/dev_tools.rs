@@ -5,11 +5,14 @@ use bevy::{
     ui::UiDebugOptions,
 };
 
-use crate::screens::Screen;
+use crate::{PlayMode, screens::Screen};
 
 pub(super) fn plugin(app: &mut App) {
-    // Log `Screen` state transitions.
-    app.add_systems(Update, log_transitions::<Screen>);
+    // Log `Screen` and `PlayMode` state transitions.
+    app.add_systems(
+        Update,
+        (log_transitions::<Screen>, log_transitions::<PlayMode>),
+    );
 
     // Toggle the debug overlay for UI.
     app.add_systems(
@@ -0,0 +1,370 @@
+//! A level editor for the `Tiles` grounds, toggled with F2 in dev builds.
+//!
+//! In side-scroll mode, drag the ground's control points up and down. In
+//! top-down mode, drag obstacles around. Clicking anywhere else paints the
+//! tile with the brush picked by the number keys, right-clicking clears it,
+//! and F5 writes the level back to its file. The level is rebuilt as it's
+//! edited, so the ground and obstacles follow along live.
+
+use bevy::{
+    color::palettes::tailwind,
+    input::{InputSystem, common_conditions::input_just_pressed},
+    prelude::*,
+    ui::Val::*,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    PlayMode,
+    screens::Screen,
+    side_scroll,
+    theme::prelude::*,
+    tilemap::{LevelAssets, Tile, Tilemap},
+    top_down,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Editor>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_editor_label);
+    // Edit before anything else sees the mouse, so that clicks meant for the
+    // editor don't also walk the player somewhere.
+    app.add_systems(
+        PreUpdate,
+        (
+            toggle_editor.run_if(input_just_pressed(TOGGLE_KEY)),
+            (
+                choose_brush,
+                edit_level,
+                save_level.run_if(input_just_pressed(SAVE_KEY)),
+            )
+                .run_if(editing_tiles),
+        )
+            .chain()
+            .after(InputSystem)
+            .run_if(in_state(Screen::Gameplay).and(resource_exists::<LevelAssets>)),
+    );
+    app.add_systems(
+        Update,
+        (
+            update_editor_label,
+            draw_editor.run_if(editing_tiles.and(resource_exists::<LevelAssets>)),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+const TOGGLE_KEY: KeyCode = KeyCode::F2;
+
+const SAVE_KEY: KeyCode = KeyCode::F5;
+
+/// The brush picked by each of the number keys from 1.
+const BRUSHES: [(KeyCode, Tile); 6] = [
+    (KeyCode::Digit1, Tile::Solid),
+    (KeyCode::Digit2, Tile::Platform),
+    (KeyCode::Digit3, Tile::Wall),
+    (KeyCode::Digit4, Tile::Round),
+    (KeyCode::Digit5, Tile::Ice),
+    (KeyCode::Digit6, Tile::Spawn),
+];
+
+/// How close to a control point a click has to be to pick it up.
+const CONTROL_POINT_RADIUS: f32 = 10.0;
+
+#[derive(Resource, Debug)]
+pub struct Editor {
+    pub active: bool,
+    /// The tile that clicking paints.
+    pub brush: Tile,
+    drag: Option<Drag>,
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self {
+            active: false,
+            brush: Tile::Solid,
+            drag: None,
+        }
+    }
+}
+
+/// Something being dragged with the mouse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Drag {
+    /// The side-scroll ground's control point in a column.
+    Surface { column: usize },
+    /// A top-down obstacle tile.
+    Tile {
+        column: usize,
+        row: usize,
+        tile: Tile,
+    },
+}
+
+/// Whether the editor is open on the `Tiles` ground of the current mode.
+fn editing_tiles(
+    editor: Res<Editor>,
+    mode: Res<State<PlayMode>>,
+    side_scroll_ground: Res<side_scroll::Ground>,
+    top_down_ground: Res<top_down::Ground>,
+) -> bool {
+    editor.active
+        && match mode.get() {
+            PlayMode::SideScroll => *side_scroll_ground == side_scroll::Ground::Tiles,
+            PlayMode::TopDown => *top_down_ground == top_down::Ground::Tiles,
+        }
+}
+
+/// The level file edited in `mode`.
+fn level_handle(level_assets: &LevelAssets, mode: PlayMode) -> &Handle<Tilemap> {
+    match mode {
+        PlayMode::SideScroll => &level_assets.side_scroll,
+        PlayMode::TopDown => &level_assets.top_down,
+    }
+}
+
+/// Opens or closes the editor, switching to the `Tiles` ground on opening.
+fn toggle_editor(
+    mut editor: ResMut<Editor>,
+    mode: Res<State<PlayMode>>,
+    mut side_scroll_ground: ResMut<side_scroll::Ground>,
+    mut top_down_ground: ResMut<top_down::Ground>,
+) {
+    editor.active = !editor.active;
+    editor.drag = None;
+    if !editor.active {
+        return;
+    }
+    match mode.get() {
+        PlayMode::SideScroll => *side_scroll_ground = side_scroll::Ground::Tiles,
+        PlayMode::TopDown => *top_down_ground = top_down::Ground::Tiles,
+    }
+}
+
+fn choose_brush(input: Res<ButtonInput<KeyCode>>, mut editor: ResMut<Editor>) {
+    if let Some((_, brush)) = BRUSHES.iter().find(|(key, _)| input.just_pressed(*key)) {
+        editor.brush = *brush;
+    }
+}
+
+/// The point in the world under the cursor.
+fn cursor_position(window: &Window, cameras: &Query<(&Camera, &GlobalTransform)>) -> Option<Vec2> {
+    let cursor = window.cursor_position()?;
+    let (camera, camera_transform) = cameras.iter().find(|(camera, _)| camera.is_active)?;
+    camera.viewport_to_world_2d(camera_transform, cursor).ok()
+}
+
+fn edit_level(
+    mut editor: ResMut<Editor>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mode: Res<State<PlayMode>>,
+    level_assets: Res<LevelAssets>,
+    mut tilemaps: ResMut<Assets<Tilemap>>,
+) {
+    let pressed = mouse.just_pressed(MouseButton::Left);
+    let erase = mouse.just_pressed(MouseButton::Right);
+    mouse.clear_just_pressed(MouseButton::Left);
+    mouse.clear_just_pressed(MouseButton::Right);
+    if !mouse.pressed(MouseButton::Left) {
+        editor.drag = None;
+    }
+    // Borrowing the level to edit marks it as edited, which rebuilds it.
+    if !pressed && !erase && editor.drag.is_none() {
+        return;
+    }
+
+    let Some(cursor) = cursor_position(&window, &cameras) else {
+        return;
+    };
+    let Some(tilemap) = tilemaps.get_mut(level_handle(&level_assets, *mode.get())) else {
+        return;
+    };
+
+    if let Some(Drag::Surface { column }) = editor.drag {
+        let centre = tilemap.tile_centre(column, 0);
+        if let Some((_, row)) = tilemap.cell_at(Vec2::new(centre.x, cursor.y)) {
+            let top = (0..tilemap.height()).find(|&row| tilemap.tile(column, row) == Tile::Solid);
+            if top != Some(row) {
+                tilemap.set_surface(column, row);
+            }
+        }
+        return;
+    }
+
+    let Some((column, row)) = tilemap.cell_at(cursor) else {
+        return;
+    };
+    let tile = tilemap.tile(column, row);
+
+    if let Some(Drag::Tile {
+        column: from_column,
+        row: from_row,
+        tile: dragged,
+    }) = editor.drag
+    {
+        if (column, row) != (from_column, from_row) && tile == Tile::Empty {
+            tilemap.set_tile(from_column, from_row, Tile::Empty);
+            tilemap.set_tile(column, row, dragged);
+            editor.drag = Some(Drag::Tile {
+                column,
+                row,
+                tile: dragged,
+            });
+        }
+        return;
+    }
+
+    if erase && tile != Tile::Empty {
+        tilemap.set_tile(column, row, Tile::Empty);
+        return;
+    }
+    if !pressed {
+        return;
+    }
+
+    editor.drag = match mode.get() {
+        PlayMode::SideScroll => tilemap
+            .ground_profile()
+            .points()
+            .iter()
+            .position(|point| point.distance(cursor) <= CONTROL_POINT_RADIUS)
+            .map(|column| Drag::Surface { column }),
+        PlayMode::TopDown => {
+            matches!(tile, Tile::Solid | Tile::Round).then_some(Drag::Tile { column, row, tile })
+        }
+    };
+    if editor.drag.is_some() || tile == editor.brush {
+        return;
+    }
+
+    let spawns: Vec<_> = (0..tilemap.height())
+        .flat_map(|row| (0..tilemap.width()).map(move |column| (column, row)))
+        .filter(|&(column, row)| tilemap.tile(column, row) == Tile::Spawn)
+        .collect();
+    // There's only one spawn point to move around.
+    if editor.brush == Tile::Spawn {
+        for (column, row) in spawns {
+            tilemap.set_tile(column, row, Tile::Empty);
+        }
+    }
+    tilemap.set_tile(column, row, editor.brush);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_level(
+    mode: Res<State<PlayMode>>,
+    level_assets: Res<LevelAssets>,
+    tilemaps: Res<Assets<Tilemap>>,
+) {
+    use bevy::asset::io::file::FileAssetReader;
+
+    let (path, handle) = match mode.get() {
+        PlayMode::SideScroll => (LevelAssets::SIDE_SCROLL_PATH, &level_assets.side_scroll),
+        PlayMode::TopDown => (LevelAssets::TOP_DOWN_PATH, &level_assets.top_down),
+    };
+    let Some(tilemap) = tilemaps.get(handle) else {
+        return;
+    };
+    let path = FileAssetReader::get_base_path().join("assets").join(path);
+    // Keep the comments heading the file, which the data doesn't hold.
+    let old = std::fs::read_to_string(&path).unwrap_or_default();
+    let header: String = old
+        .lines()
+        .take_while(|line| line.trim_start().starts_with("//"))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let result = ron::ser::to_string_pretty(tilemap, default())
+        .map_err(|error| error.to_string())
+        .and_then(|text| {
+            std::fs::write(&path, header + &text + "\n").map_err(|error| error.to_string())
+        });
+    match result {
+        Ok(()) => info!("Saved level to {}", path.display()),
+        Err(error) => warn!("Failed to save level: {error}"),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn save_level() {
+    warn!("Levels can't be saved from the web");
+}
+
+#[derive(Component, Debug)]
+struct EditorLabel;
+
+fn spawn_editor_label(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Editor Label"),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Px(20.0),
+            top: Px(20.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![(widget::label(""), EditorLabel)],
+    ));
+}
+
+fn update_editor_label(
+    editor: Res<Editor>,
+    mut labels: Query<(&mut Text, &mut Visibility), With<EditorLabel>>,
+) {
+    let value = format!(
+        "Editing: brush {} (1-6), F5 to save",
+        editor.brush.to_char()
+    );
+    for (mut text, mut visibility) in &mut labels {
+        visibility.set_if_neq(if editor.active {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        if text.0 != value {
+            text.0 = value.clone();
+        }
+    }
+}
+
+/// Outlines the map and the tile under the cursor, and marks the side-scroll
+/// ground's control points.
+fn draw_editor(
+    editor: Res<Editor>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mode: Res<State<PlayMode>>,
+    level_assets: Res<LevelAssets>,
+    tilemaps: Res<Assets<Tilemap>>,
+    mut gizmos: Gizmos,
+) {
+    let Some(tilemap) = tilemaps.get(level_handle(&level_assets, *mode.get())) else {
+        return;
+    };
+    let size = Vec2::new(tilemap.width() as f32, tilemap.height() as f32) * tilemap.tile_size;
+    gizmos.rect_2d(size / 2.0, size, tailwind::STONE_500);
+
+    if let Some((column, row)) =
+        cursor_position(&window, &cameras).and_then(|cursor| tilemap.cell_at(cursor))
+    {
+        gizmos.rect_2d(
+            tilemap.tile_centre(column, row),
+            Vec2::splat(tilemap.tile_size),
+            tailwind::AMBER_400,
+        );
+    }
+
+    if *mode.get() == PlayMode::SideScroll {
+        for (column, point) in tilemap.ground_profile().points().iter().enumerate() {
+            let radius = if editor.drag == Some(Drag::Surface { column }) {
+                CONTROL_POINT_RADIUS * 1.5
+            } else {
+                CONTROL_POINT_RADIUS
+            };
+            gizmos.circle_2d(*point, radius, tailwind::AMBER_400);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Solve for the tuning that produces a given feel, measured with the same
+//! manoeuvres and integration scheme as the [`sweep`](crate::sweep) tool.
+
+use std::fmt;
+
+use crate::{MotionParameters, sweep};
+
+/// A designer-facing target that a tuning is solved for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeelTarget {
+    TimeTo90,
+    StoppingDistance,
+    ReversalTime,
+    TurnRadius,
+}
+
+impl fmt::Display for FeelTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeelTarget::TimeTo90 => write!(f, "time to 90% speed"),
+            FeelTarget::StoppingDistance => write!(f, "stopping distance"),
+            FeelTarget::ReversalTime => write!(f, "reversal time"),
+            FeelTarget::TurnRadius => write!(f, "turn radius"),
+        }
+    }
+}
+
+/// The target that no tuning can reach, given the targets solved before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnreachableTarget(pub FeelTarget);
+
+impl fmt::Display for UnreachableTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no tuning reaches the requested {}", self.0)
+    }
+}
+
+impl std::error::Error for UnreachableTarget {}
+
+/// The range searched for each parameter. Targets outside what this range
+/// can reach are reported as unreachable.
+const SEARCH_RANGE: (f32, f32) = (1e-3, 1e3);
+
+/// Steps of bisection, each halving the search range in log space.
+const ITERATIONS: u32 = 60;
+
+impl MotionParameters {
+    /// The tuning that reaches 90% of `max_speed` from rest in `time_to_90`
+    /// seconds, stops from full speed within `stopping_distance`, and goes
+    /// from full speed one way to 90% of it the other way in `reversal_time`
+    /// seconds. Turning is damped like reversing, as in [`Self::full`].
+    ///
+    /// Reversing includes accelerating back up from rest, so `reversal_time`
+    /// must lie between `time_to_90` and about `time_to_90 * (1 + 1 / ln 10)`,
+    /// where reversing is undamped.
+    pub fn from_feel(
+        max_speed: f32,
+        time_to_90: f32,
+        stopping_distance: f32,
+        reversal_time: f32,
+    ) -> Result<Self, UnreachableTarget> {
+        let mut params = Self::solve_common(max_speed, time_to_90, stopping_distance)?;
+        params.alpha_rev = solve(reversal_time, |alpha_rev| {
+            let trial = Self {
+                alpha_rev,
+                ..params
+            };
+            sweep::top_down_metrics(&trial).reversal_time
+        })
+        .ok_or(UnreachableTarget(FeelTarget::ReversalTime))?;
+        params.alpha_turn = params.alpha_rev;
+        Ok(params)
+    }
+
+    /// Like [`Self::from_feel`], but with turning damped separately so that a
+    /// top-down player at full speed carries on `turn_radius` in its original
+    /// direction after the intent turns 90°.
+    pub fn from_feel_with_turn(
+        max_speed: f32,
+        time_to_90: f32,
+        stopping_distance: f32,
+        reversal_time: f32,
+        turn_radius: f32,
+    ) -> Result<Self, UnreachableTarget> {
+        let mut params = Self::from_feel(max_speed, time_to_90, stopping_distance, reversal_time)?;
+        params.alpha_turn = solve(turn_radius, |alpha_turn| {
+            let trial = Self {
+                alpha_turn,
+                ..params
+            };
+            sweep::top_down_metrics(&trial).turn_radius
+        })
+        .ok_or(UnreachableTarget(FeelTarget::TurnRadius))?;
+        Ok(params)
+    }
+
+    fn solve_common(
+        max_speed: f32,
+        time_to_90: f32,
+        stopping_distance: f32,
+    ) -> Result<Self, UnreachableTarget> {
+        let base = Self::basic(max_speed, 1.0);
+        // Only `t_acc` affects acceleration from rest, so solve for it first.
+        let t_acc = solve_increasing(time_to_90, |t_acc| {
+            sweep::top_down_metrics(&Self { t_acc, ..base }).time_to_90
+        })
+        .ok_or(UnreachableTarget(FeelTarget::TimeTo90))?;
+        let alpha_stop = solve(stopping_distance, |alpha_stop| {
+            sweep::top_down_metrics(&Self {
+                t_acc,
+                alpha_stop,
+                ..base
+            })
+            .stopping_distance
+        })
+        .ok_or(UnreachableTarget(FeelTarget::StoppingDistance))?;
+        Ok(Self {
+            t_acc,
+            alpha_stop,
+            ..base
+        })
+    }
+}
+
+/// Finds the parameter at which a metric that shrinks as the parameter grows
+/// equals `target`. A metric of `None` counts as never finishing.
+fn solve(target: f32, metric: impl Fn(f32) -> Option<f32>) -> Option<f32> {
+    bisect(-target, |parameter| {
+        -metric(parameter).unwrap_or(f32::INFINITY)
+    })
+}
+
+/// Like [`solve`], for a metric that grows with the parameter.
+fn solve_increasing(target: f32, metric: impl Fn(f32) -> Option<f32>) -> Option<f32> {
+    bisect(target, |parameter| {
+        metric(parameter).unwrap_or(f32::INFINITY)
+    })
+}
+
+/// Bisects [`SEARCH_RANGE`] in log space for where the increasing function
+/// `f` crosses `goal`, or `None` if it doesn't within the range.
+fn bisect(goal: f32, f: impl Fn(f32) -> f32) -> Option<f32> {
+    if !goal.is_finite() || goal == 0.0 {
+        return None;
+    }
+    let (mut low, mut high) = (SEARCH_RANGE.0.ln(), SEARCH_RANGE.1.ln());
+    if f(low.exp()) > goal || f(high.exp()) < goal {
+        return None;
+    }
+    for _ in 0..ITERATIONS {
+        let middle = 0.5 * (low + high);
+        if f(middle.exp()) < goal {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Some((0.5 * (low + high)).exp())
+}
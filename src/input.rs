@@ -0,0 +1,97 @@
+//! Rebindable keyboard controls.
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::{Deserialize, Serialize};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<KeyBindings>();
+}
+
+/// Something the player can do with a key press.
+#[derive(Serialize, Deserialize, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Jump,
+    Pause,
+    SwitchMode,
+    CycleGround,
+    CycleTrail,
+    CyclePreset,
+    ToggleVectors,
+    ToggleStopPoint,
+    ToggleMute,
+}
+
+/// The keys bound to each [`Action`]. Any action missing from a saved set of
+/// bindings keeps its default keys.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(
+    from = "HashMap<Action, Vec<KeyCode>>",
+    into = "HashMap<Action, Vec<KeyCode>>"
+)]
+pub struct KeyBindings(HashMap<Action, Vec<KeyCode>>);
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        use Action::*;
+        Self(HashMap::from_iter([
+            (MoveLeft, vec![KeyCode::KeyA, KeyCode::ArrowLeft]),
+            (MoveRight, vec![KeyCode::KeyD, KeyCode::ArrowRight]),
+            (MoveUp, vec![KeyCode::KeyW, KeyCode::ArrowUp]),
+            (MoveDown, vec![KeyCode::KeyS, KeyCode::ArrowDown]),
+            (Jump, vec![KeyCode::Space]),
+            (Pause, vec![KeyCode::Escape]),
+            (SwitchMode, vec![KeyCode::Tab]),
+            (CycleGround, vec![KeyCode::KeyC]),
+            (CycleTrail, vec![KeyCode::KeyT]),
+            (CyclePreset, vec![KeyCode::KeyP]),
+            (ToggleVectors, vec![KeyCode::KeyV]),
+            (ToggleStopPoint, vec![KeyCode::KeyX]),
+            (ToggleMute, vec![KeyCode::KeyM]),
+        ]))
+    }
+}
+
+impl From<HashMap<Action, Vec<KeyCode>>> for KeyBindings {
+    fn from(bindings: HashMap<Action, Vec<KeyCode>>) -> Self {
+        let mut merged = Self::default();
+        merged.0.extend(bindings);
+        merged
+    }
+}
+
+impl From<KeyBindings> for HashMap<Action, Vec<KeyCode>> {
+    fn from(bindings: KeyBindings) -> Self {
+        bindings.0
+    }
+}
+
+impl KeyBindings {
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn bind(&mut self, action: Action, keys: Vec<KeyCode>) {
+        self.0.insert(action, keys);
+    }
+
+    pub fn pressed(&self, action: Action, input: &ButtonInput<KeyCode>) -> bool {
+        input.any_pressed(self.keys(action).iter().copied())
+    }
+
+    pub fn just_pressed(&self, action: Action, input: &ButtonInput<KeyCode>) -> bool {
+        input.any_just_pressed(self.keys(action).iter().copied())
+    }
+}
+
+/// Run condition that is true on the frame any key bound to `action` is pressed.
+pub fn action_just_pressed(
+    action: Action,
+) -> impl FnMut(Res<ButtonInput<KeyCode>>, Res<KeyBindings>) -> bool + Clone {
+    move |input: Res<ButtonInput<KeyCode>>, bindings: Res<KeyBindings>| {
+        bindings.just_pressed(action, &input)
+    }
+}
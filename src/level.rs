@@ -4,38 +4,83 @@ use bevy::prelude::*;
 
 use crate::{
     PlayMode,
+    animation::PlayerAssets,
+    npc::{Behaviour, Crowd},
+    player::{CameraOf, PlayerCamera},
     screens::Screen,
     side_scroll::{self},
-    top_down::{self, GroundMaterial},
+    top_down,
 };
 
-/// A system that spawns the main level.
+/// Marks the root entity of the spawned level.
+#[derive(Component, Debug, Default)]
+pub struct LevelRoot;
+
+/// A system that spawns the main level for the current [`PlayMode`]. The level
+/// is despawned when leaving gameplay or switching mode.
 pub fn spawn_level(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut mats: ResMut<Assets<ColorMaterial>>,
-    mut ground_mats: ResMut<Assets<GroundMaterial>>,
-    mode: Res<PlayMode>,
+    player_assets: Res<PlayerAssets>,
+    player_camera: Res<PlayerCamera>,
+    crowd: Res<Crowd>,
+    mode: Res<State<PlayMode>>,
 ) {
-    let mut player = commands.spawn((
-        Name::new("Level"),
-        Transform::default(),
-        Visibility::default(),
-        StateScoped(Screen::Gameplay),
-    ));
-    match *mode {
+    let level = commands
+        .spawn((
+            Name::new("Level"),
+            LevelRoot,
+            Transform::default(),
+            Visibility::default(),
+            StateScoped(Screen::Gameplay),
+            StateScoped(*mode.get()),
+        ))
+        .id();
+    let player = match mode.get() {
         PlayMode::SideScroll => {
-            player.insert(children![side_scroll::player::player(
-                &mut meshes,
-                &mut mats
-            )]);
+            commands.spawn((side_scroll::player::player(&player_assets), ChildOf(level)))
         }
         PlayMode::TopDown => {
-            player.insert(children![top_down::player::player(
-                &mut meshes,
-                &mut mats,
-                &mut ground_mats
-            )]);
+            commands.spawn((top_down::player::player(&player_assets), ChildOf(level)))
         }
-    };
+    }
+    .id();
+
+    // Spread the crowd out on both sides of the player.
+    for index in 0..crowd.0 {
+        let side = if index % 2 == 0 { 1.0 } else { -1.0 };
+        let distance = 150.0 * (index / 2 + 1) as f32;
+        match mode.get() {
+            PlayMode::SideScroll => {
+                let home = Vec2::new(side * distance, 0.0);
+                commands.spawn((
+                    side_scroll::player::npc(
+                        &player_assets,
+                        home.x,
+                        Behaviour::crowd_member(index, player, home),
+                    ),
+                    ChildOf(level),
+                ))
+            }
+            PlayMode::TopDown => {
+                let home = side * distance * Vec2::from_angle(index as f32);
+                commands.spawn((
+                    top_down::player::npc(
+                        &player_assets,
+                        home,
+                        Behaviour::crowd_member(index, player, home),
+                    ),
+                    ChildOf(level),
+                ))
+            }
+        };
+    }
+
+    if player_camera.0 {
+        match mode.get() {
+            PlayMode::SideScroll => {
+                commands.spawn((side_scroll::player::camera(), CameraOf(player)))
+            }
+            PlayMode::TopDown => commands.spawn((top_down::player::camera(), CameraOf(player))),
+        };
+    }
 }
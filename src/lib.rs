@@ -3,19 +3,58 @@
 // Disable console on Windows for non-dev builds.
 #![cfg_attr(not(feature = "dev"), windows_subsystem = "windows")]
 
+pub mod animation;
 pub mod asset_tracking;
+pub mod audio;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod cli;
 #[cfg(feature = "dev")]
 pub mod dev_tools;
+#[cfg(feature = "dev")]
+pub mod editor;
+pub mod feel;
+pub mod input;
 pub mod level;
+pub mod menus;
+pub mod motion_audio;
+pub mod npc;
 pub mod player;
+pub mod pointer;
+pub mod prediction;
+pub mod presets;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod replay;
 pub mod screens;
+pub mod settings;
 pub mod side_scroll;
+pub mod sweep;
 pub mod theme;
+pub mod tilemap;
 pub mod top_down;
+pub mod trace;
+pub mod trail;
+
+use std::time::Duration;
 
-use bevy::{asset::AssetMetaCheck, prelude::*};
+use bevy::{
+    app::{PluginGroupBuilder, ScheduleRunnerPlugin},
+    asset::AssetMetaCheck,
+    prelude::*,
+    render::{RenderPlugin, settings::WgpuSettings},
+    state::app::StatesPlugin,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::wasm_bindgen;
 
+use crate::screens::Screen;
+
+#[wasm_bindgen]
+pub fn menu() {
+    run_menu();
+}
+
 #[wasm_bindgen]
 pub fn side_scroller(max_speed: f32, t_acc: f32, a_stop: f32, a_rev: f32) {
     run_side_scroll(
@@ -44,6 +83,34 @@ pub fn top_down(max_speed: f32, t_acc: f32, a_stop: f32, a_rev: f32, a_turn: f32
     );
 }
 
+#[wasm_bindgen]
+pub fn side_scroller_preset(preset: &str) {
+    run_side_scroll_preset(preset, side_scroll::Ground::FlatPeriodic);
+}
+
+#[wasm_bindgen]
+pub fn side_scroller_hills_preset(preset: &str) {
+    run_side_scroll_preset(preset, side_scroll::Ground::Hills);
+}
+
+#[wasm_bindgen]
+pub fn top_down_preset(preset: &str) {
+    run_top_down_preset(preset, top_down::Ground::FlatPeriodic);
+}
+
+/// Start on the title screen, where the play mode, ground and tuning can be chosen.
+pub fn run_menu() -> AppExit {
+    App::new()
+        .add_plugins(AppPlugin::new(
+            CharMotionPlugin::new()
+                .mode(PlayMode::SideScroll)
+                .start(Screen::Title),
+        ))
+        .run()
+}
+
+/// Start a side-scroll game. The arguments take priority over any settings
+/// saved from a previous launch.
 pub fn run_side_scroll(
     max_speed: f32,
     t_acc: f32,
@@ -52,14 +119,17 @@ pub fn run_side_scroll(
     ground: side_scroll::Ground,
 ) -> AppExit {
     App::new()
-        .add_plugins(AppPlugin {
-            mode: PlayMode::SideScroll,
-            params: MotionParameters::full(max_speed, t_acc, a_stop, a_rev),
-        })
-        .insert_resource(ground)
+        .add_plugins(AppPlugin::new(
+            CharMotionPlugin::new()
+                .mode(PlayMode::SideScroll)
+                .params(MotionParameters::full(max_speed, t_acc, a_stop, a_rev))
+                .ground(ground),
+        ))
         .run()
 }
 
+/// Start a top-down game. The arguments take priority over any settings
+/// saved from a previous launch.
 pub fn run_top_down(
     max_speed: f32,
     t_acc: f32,
@@ -69,23 +139,56 @@ pub fn run_top_down(
     ground: top_down::Ground,
 ) -> AppExit {
     App::new()
-        .add_plugins(AppPlugin {
-            mode: PlayMode::TopDown,
-            params: MotionParameters::full_with_separate_turn(
-                max_speed, t_acc, a_stop, a_rev, a_turn,
-            ),
-        })
-        .insert_resource(ground)
+        .add_plugins(AppPlugin::new(
+            CharMotionPlugin::new()
+                .mode(PlayMode::TopDown)
+                .params(MotionParameters::full_with_separate_turn(
+                    max_speed, t_acc, a_stop, a_rev, a_turn,
+                ))
+                .ground(ground),
+        ))
+        .run()
+}
+
+/// Start a side-scroll game with the named tuning from the presets file,
+/// which takes priority over any saved tuning.
+pub fn run_side_scroll_preset(preset: &str, ground: side_scroll::Ground) -> AppExit {
+    App::new()
+        .add_plugins(AppPlugin::new(
+            CharMotionPlugin::new()
+                .mode(PlayMode::SideScroll)
+                .params(MotionParameters::basic(500.0, 1.0))
+                .ground(ground),
+        ))
+        .insert_resource(presets::RequestedPreset(preset.to_string()))
+        .run()
+}
+
+/// Start a top-down game with the named tuning from the presets file,
+/// which takes priority over any saved tuning.
+pub fn run_top_down_preset(preset: &str, ground: top_down::Ground) -> AppExit {
+    App::new()
+        .add_plugins(AppPlugin::new(
+            CharMotionPlugin::new()
+                .mode(PlayMode::TopDown)
+                .params(MotionParameters::basic(1000.0, 1.0))
+                .ground(ground),
+        ))
+        .insert_resource(presets::RequestedPreset(preset.to_string()))
         .run()
 }
 
-#[derive(Clone, Copy, Resource)]
+/// Which kind of game is being played. Both modes' plugins are always
+/// registered, with their systems gated on this state.
+#[derive(States, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[states(scoped_entities)]
 pub enum PlayMode {
     TopDown,
+    #[default]
     SideScroll,
 }
 
-#[derive(Clone, Copy, Resource)]
+#[derive(Clone, Copy, Debug, PartialEq, Resource, Serialize, Deserialize)]
 pub struct MotionParameters {
     max_speed: f32,
     alpha_rev: f32,
@@ -95,6 +198,12 @@ pub struct MotionParameters {
     gravity_strength: f32,
 }
 
+impl Default for MotionParameters {
+    fn default() -> Self {
+        Self::basic(500.0, 1.0)
+    }
+}
+
 impl MotionParameters {
     pub fn basic(max_speed: f32, t_acc: f32) -> Self {
         Self {
@@ -145,6 +254,91 @@ impl MotionParameters {
             gravity_strength: 20.0,
         }
     }
+
+    /// The speed reached when holding a direction on flat ground.
+    pub fn max_speed(&self) -> f32 {
+        self.max_speed
+    }
+
+    /// The time scale over which the controller approaches `max_speed`.
+    pub fn t_acc(&self) -> f32 {
+        self.t_acc
+    }
+
+    /// Damping applied when there is no intent.
+    pub fn alpha_stop(&self) -> f32 {
+        self.alpha_stop
+    }
+
+    /// Damping applied when moving against the intent.
+    pub fn alpha_rev(&self) -> f32 {
+        self.alpha_rev
+    }
+
+    /// Damping applied to velocity across the intent in top-down mode.
+    pub fn alpha_turn(&self) -> f32 {
+        self.alpha_turn
+    }
+
+    /// How strongly slopes speed up or slow down a side-scroll controller.
+    pub fn gravity_strength(&self) -> f32 {
+        self.gravity_strength
+    }
+
+    /// How far a controller moving at `speed` on flat ground travels after the
+    /// intent is released. Each step divides the velocity by the same factor,
+    /// so the distances form a geometric series whose sum doesn't depend on
+    /// the timestep.
+    pub fn stopping_distance(&self, speed: f32) -> f32 {
+        speed * self.t_acc / self.alpha_stop
+    }
+
+    /// How far a top-down controller moving at `speed` across its intent
+    /// carries on sideways before the intent wins, summed the same way as
+    /// [`Self::stopping_distance`].
+    pub fn turning_distance(&self, speed: f32) -> f32 {
+        speed * self.t_acc / self.alpha_turn
+    }
+
+    /// Sets the speed reached when holding a direction on flat ground.
+    pub fn set_max_speed(&mut self, max_speed: f32) {
+        self.max_speed = max_speed;
+    }
+
+    /// Sets the time scale over which the controller approaches `max_speed`.
+    pub fn set_t_acc(&mut self, t_acc: f32) {
+        self.t_acc = t_acc;
+    }
+
+    /// Sets the damping applied when there is no intent.
+    pub fn set_alpha_stop(&mut self, alpha_stop: f32) {
+        self.alpha_stop = alpha_stop;
+    }
+
+    /// Sets the damping applied when moving against the intent.
+    pub fn set_alpha_rev(&mut self, alpha_rev: f32) {
+        self.alpha_rev = alpha_rev;
+    }
+
+    /// Sets the damping applied to velocity across the intent in top-down mode.
+    pub fn set_alpha_turn(&mut self, alpha_turn: f32) {
+        self.alpha_turn = alpha_turn;
+    }
+
+    /// Sets how strongly slopes speed up or slow down a side-scroll controller.
+    pub fn set_gravity_strength(&mut self, gravity_strength: f32) {
+        self.gravity_strength = gravity_strength;
+    }
+}
+
+impl PlayMode {
+    /// The other play mode.
+    pub fn toggled(self) -> Self {
+        match self {
+            PlayMode::TopDown => PlayMode::SideScroll,
+            PlayMode::SideScroll => PlayMode::TopDown,
+        }
+    }
 }
 
 impl std::fmt::Display for PlayMode {
@@ -156,64 +350,317 @@ impl std::fmt::Display for PlayMode {
     }
 }
 
-pub struct AppPlugin {
+/// The character motion game, without any of Bevy's own plugins, so that it
+/// can be embedded in an existing [`App`]. It expects [`DefaultPlugins`] or
+/// an equivalent set to have been added first.
+///
+/// ```ignore
+/// # use bevy::prelude::*;
+/// # use char_motion_example::{CharMotionPlugin, MotionParameters, PlayMode, side_scroll};
+/// App::new()
+///     .add_plugins(DefaultPlugins)
+///     .add_plugins(
+///         CharMotionPlugin::new()
+///             .mode(PlayMode::SideScroll)
+///             .params(MotionParameters::full(500.0, 1.0, 5.0, 5.0))
+///             .ground(side_scroll::Ground::Hills)
+///             .camera(false),
+///     )
+///     .run();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CharMotionPlugin {
     mode: PlayMode,
-    params: MotionParameters,
+    params: Option<MotionParameters>,
+    side_scroll_ground: Option<side_scroll::Ground>,
+    top_down_ground: Option<top_down::Ground>,
+    terrain: Option<side_scroll::EndlessTerrain>,
+    camera: bool,
+    npcs: usize,
+    start: Screen,
+    persist_settings: bool,
+}
+
+impl Default for CharMotionPlugin {
+    fn default() -> Self {
+        Self {
+            mode: PlayMode::default(),
+            params: None,
+            side_scroll_ground: None,
+            top_down_ground: None,
+            terrain: None,
+            camera: true,
+            npcs: 0,
+            start: Screen::Gameplay,
+            persist_settings: false,
+        }
+    }
+}
+
+impl CharMotionPlugin {
+    /// Straight into a side-scroll game with a basic tuning, following the
+    /// player with its own camera.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The play mode to start in.
+    pub fn mode(mut self, mode: PlayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// The tuning to start with, instead of the last one saved.
+    pub fn params(mut self, params: MotionParameters) -> Self {
+        self.params = Some(params);
+        self
+    }
+
+    /// The ground to start on in the mode the ground belongs to, instead of
+    /// the last one saved.
+    pub fn ground(mut self, ground: impl Into<LevelGround>) -> Self {
+        match ground.into() {
+            LevelGround::SideScroll(ground) => self.side_scroll_ground = Some(ground),
+            LevelGround::TopDown(ground) => self.top_down_ground = Some(ground),
+        }
+        self
+    }
+
+    /// The seed and difficulty of the [`Endless`](side_scroll::Ground::Endless)
+    /// side-scroll ground.
+    pub fn terrain(mut self, terrain: side_scroll::EndlessTerrain) -> Self {
+        self.terrain = Some(terrain);
+        self
+    }
+
+    /// Whether each player spawns with a camera that follows it. Without one,
+    /// add [`CameraOf`](player::CameraOf) to a camera of your own to follow the player.
+    pub fn camera(mut self, camera: bool) -> Self {
+        self.camera = camera;
+        self
+    }
+
+    /// How many NPCs to spawn around the player, as a [`Crowd`](npc::Crowd).
+    pub fn npcs(mut self, npcs: usize) -> Self {
+        self.npcs = npcs;
+        self
+    }
+
+    /// The screen to show once loading is done, such as [`Screen::Title`].
+    pub fn start(mut self, start: Screen) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Whether to restore settings saved by a previous launch, for whatever
+    /// isn't given here, and to save them as the user changes them. Off by
+    /// default, so that an app embedding the game only writes to the user's
+    /// config directory if it asks to.
+    pub fn persist_settings(mut self, persist_settings: bool) -> Self {
+        self.persist_settings = persist_settings;
+        self
+    }
+}
+
+/// The ground of either play mode, for [`CharMotionPlugin::ground`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelGround {
+    SideScroll(side_scroll::Ground),
+    TopDown(top_down::Ground),
+}
+
+impl From<side_scroll::Ground> for LevelGround {
+    fn from(ground: side_scroll::Ground) -> Self {
+        Self::SideScroll(ground)
+    }
+}
+
+impl From<top_down::Ground> for LevelGround {
+    fn from(ground: top_down::Ground) -> Self {
+        Self::TopDown(ground)
+    }
+}
+
+impl Plugin for CharMotionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((SimulationPlugins, PresentationPlugins));
+        if self.persist_settings {
+            app.add_plugins(settings::plugin);
+        }
+
+        app.insert_state(self.mode);
+        app.insert_resource(settings::LaunchChoices {
+            params: self.params.is_some(),
+            side_scroll_ground: self.side_scroll_ground.is_some(),
+            top_down_ground: self.top_down_ground.is_some(),
+        });
+        if let Some(params) = self.params {
+            app.insert_resource(params);
+        }
+        app.insert_resource(player::PlayerCamera(self.camera));
+        app.insert_resource(npc::Crowd(self.npcs));
+        app.insert_resource(screens::AfterLoading(self.start));
+        if let Some(ground) = self.side_scroll_ground {
+            app.insert_resource(ground);
+        }
+        if let Some(ground) = self.top_down_ground {
+            app.insert_resource(ground);
+        }
+        if let Some(terrain) = self.terrain {
+            app.insert_resource(terrain);
+        }
+    }
+}
+
+/// The movement controllers and the mapping of their motion onto each
+/// ground, with no input, assets, rendering or UI. These run with just
+/// [`MinimalPlugins`], for tests, servers and batch simulations.
+///
+/// Spawn entities with a [`BasicMovementController`](side_scroll::movement::BasicMovementController)
+/// and [`PositionAlongGround`](side_scroll::level::PositionAlongGround), or a
+/// [`MovementController`](top_down::movement::MovementController), then steer
+/// them by setting their [`MovementIntent`](player::MovementIntent), or by
+/// giving them a [`Behaviour`](npc::Behaviour) and an [`Npc`](npc::Npc)
+/// marker. The [`PlayMode`] state picks which kind of controller is simulated.
+pub struct SimulationPlugins;
+
+impl PluginGroup for SimulationPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(simulation_plugin)
+            .add(player::plugin)
+            .add(npc::plugin)
+            .add(side_scroll::simulation_plugin)
+            .add(top_down::simulation_plugin)
+    }
+}
+
+fn simulation_plugin(app: &mut App) {
+    if !app.is_plugin_added::<StatesPlugin>() {
+        app.add_plugins(StatesPlugin);
+    }
+    app.init_state::<PlayMode>();
+    app.init_resource::<MotionParameters>();
+
+    // Order new `AppSystems` variants by adding them here:
+    app.configure_sets(
+        Update,
+        (
+            AppSystems::TickTimers,
+            AppSystems::RecordInput,
+            AppSystems::Update,
+        )
+            .chain(),
+    );
+}
+
+/// Everything that shows the simulation and lets a player drive it: screens,
+/// menus, keyboard, mouse and touch input, sprites, audio, cameras and debug
+/// drawing. Needs [`DefaultPlugins`] and [`SimulationPlugins`].
+pub struct PresentationPlugins;
+
+impl PluginGroup for PresentationPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        let group = PluginGroupBuilder::start::<Self>()
+            .add(presentation_plugin)
+            // Set up asset tracking first, as other plugins load resources with it.
+            .add(asset_tracking::plugin)
+            .add(animation::plugin)
+            .add(audio::plugin);
+        #[cfg(feature = "dev")]
+        let group = group.add(dev_tools::plugin).add(editor::plugin);
+        let group = group
+            .add(input::plugin)
+            .add(menus::plugin)
+            .add(motion_audio::plugin)
+            .add(npc::presentation_plugin)
+            .add(pointer::plugin)
+            .add(prediction::plugin)
+            .add(presets::plugin)
+            .add(screens::plugin)
+            .add(theme::plugin)
+            .add(tilemap::plugin)
+            .add(trace::plugin)
+            .add(trail::plugin)
+            .add(side_scroll::presentation_plugin)
+            .add(top_down::presentation_plugin);
+        #[cfg(not(target_arch = "wasm32"))]
+        let group = group.add(replay::plugin);
+        group
+    }
+}
+
+fn presentation_plugin(app: &mut App) {
+    // Set up the `Pause` state.
+    app.configure_sets(
+        Update,
+        PausableSystems.run_if(in_state(screens::Pause::Running)),
+    );
+}
+
+/// The standalone app: [`CharMotionPlugin`] with Bevy's default plugins set
+/// up for its window.
+struct AppPlugin {
+    game: CharMotionPlugin,
+    /// Size of the window in logical pixels, when not on the web.
+    window_size: Vec2,
+    /// Run without a window or renderer, for example to simulate from the command line.
+    headless: bool,
+}
+
+impl AppPlugin {
+    fn new(game: CharMotionPlugin) -> Self {
+        Self {
+            // The standalone app is the user's own, so it remembers their settings.
+            game: game.persist_settings(true),
+            window_size: Vec2::new(1280.0, 720.0),
+            headless: false,
+        }
+    }
 }
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
         // Add Bevy plugins.
-        app.add_plugins(
-            DefaultPlugins
-                .set(AssetPlugin {
-                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
-                    // This causes errors and even panics on web build on itch.
-                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
-                    meta_check: AssetMetaCheck::Never,
+        let mut bevy_plugins = DefaultPlugins
+            .set(AssetPlugin {
+                // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                // This causes errors and even panics on web build on itch.
+                // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            .set(WindowPlugin {
+                primary_window: (!self.headless).then(|| Window {
+                    title: "character motion example".into(),
+                    resolution: self.window_size.into(),
+                    fit_canvas_to_parent: true,
                     ..default()
-                })
-                .set(WindowPlugin {
-                    primary_window: Window {
-                        title: "character motion example".into(),
-                        fit_canvas_to_parent: true,
+                }),
+                exit_condition: if self.headless {
+                    ExitCondition::DontExit
+                } else {
+                    ExitCondition::OnPrimaryClosed
+                },
+                ..default()
+            });
+        if self.headless {
+            bevy_plugins = bevy_plugins
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
                         ..default()
                     }
                     .into(),
                     ..default()
-                }),
-        );
-
-        // Add other plugins.
-        app.add_plugins((
-            asset_tracking::plugin,
-            #[cfg(feature = "dev")]
-            dev_tools::plugin,
-            screens::plugin,
-            theme::plugin,
-        ));
-
-        match self.mode {
-            PlayMode::TopDown => app.add_plugins(top_down::plugin),
-            PlayMode::SideScroll => app.add_plugins(side_scroll::plugin),
-        };
-
-        // Order new `AppSystems` variants by adding them here:
-        app.configure_sets(
-            Update,
-            (
-                AppSystems::TickTimers,
-                AppSystems::RecordInput,
-                AppSystems::Update,
-            )
-                .chain(),
-        );
-
-        // Spawn the main camera.
-        // app.add_systems(Startup, spawn_camera);
+                })
+                .disable::<WinitPlugin>();
+            // Without a display to pace frames, run them back to back.
+            app.add_plugins(ScheduleRunnerPlugin::run_loop(Duration::ZERO));
+        }
+        app.add_plugins(bevy_plugins);
 
-        app.insert_resource(self.mode);
-        app.insert_resource(self.params);
+        app.add_plugins(self.game.clone());
     }
 }
 
@@ -230,6 +677,10 @@ enum AppSystems {
     Update,
 }
 
+/// A system set for systems that shouldn't run while the game is paused.
+#[derive(SystemSet, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct PausableSystems;
+
 // fn spawn_camera(mut commands: Commands) {
 //     commands.spawn((
 //         Name::new("Camera"),
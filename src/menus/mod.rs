@@ -0,0 +1,10 @@
+//! The menus shown over gameplay while it is paused.
+
+mod pause;
+mod settings;
+
+use bevy::prelude::*;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_plugins((pause::plugin, settings::plugin));
+}
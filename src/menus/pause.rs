@@ -0,0 +1,49 @@
+//! The pause menu, shown over the frozen game.
+
+use bevy::prelude::*;
+
+use crate::{
+    screens::{Pause, Screen, reset_level},
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Pause::Paused), spawn_pause_menu);
+}
+
+fn spawn_pause_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Pause Menu"),
+        GlobalZIndex(2),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        StateScoped(Pause::Paused),
+        children![
+            widget::header("Paused"),
+            widget::button("Resume", resume),
+            widget::button("Reset Player", reset_player),
+            widget::button("Settings", open_settings),
+            widget::button("Back to Title", quit_to_title),
+        ],
+    ));
+}
+
+fn resume(_: Trigger<Pointer<Click>>, mut next_pause: ResMut<NextState<Pause>>) {
+    next_pause.set(Pause::Running);
+}
+
+fn reset_player(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut next_pause: ResMut<NextState<Pause>>,
+) {
+    commands.run_system_cached(reset_level);
+    next_pause.set(Pause::Running);
+}
+
+fn open_settings(_: Trigger<Pointer<Click>>, mut next_pause: ResMut<NextState<Pause>>) {
+    next_pause.set(Pause::Settings);
+}
+
+fn quit_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
@@ -0,0 +1,126 @@
+//! The settings menu, reached from the pause menu.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    audio::AudioSettings, screens::Pause, theme::prelude::*,
+    top_down::overlay::DecompositionOverlay, trail::TrailDisplay,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Pause::Settings), spawn_settings_menu);
+    app.add_systems(
+        Update,
+        update_setting_labels.run_if(in_state(Pause::Settings)),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SettingLabel {
+    Volume,
+    Muted,
+    Trail,
+    Vectors,
+}
+
+fn spawn_settings_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Settings Menu"),
+        GlobalZIndex(2),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        StateScoped(Pause::Settings),
+        children![
+            widget::header("Settings"),
+            settings_grid(),
+            widget::button("Back", go_back),
+        ],
+    ));
+}
+
+fn settings_grid() -> impl Bundle {
+    (
+        Name::new("Settings Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 300.0),
+            ..default()
+        },
+        children![
+            widget::grid_label("Volume"),
+            widget::stepper(
+                (widget::label(""), SettingLabel::Volume),
+                lower_volume,
+                raise_volume
+            ),
+            widget::grid_label("Sound"),
+            widget::stepper(
+                (widget::label(""), SettingLabel::Muted),
+                toggle_mute,
+                toggle_mute
+            ),
+            widget::grid_label("Trail"),
+            widget::stepper(
+                (widget::label(""), SettingLabel::Trail),
+                previous_trail_display,
+                next_trail_display
+            ),
+            widget::grid_label("Velocity vectors"),
+            widget::stepper(
+                (widget::label(""), SettingLabel::Vectors),
+                toggle_vectors,
+                toggle_vectors
+            ),
+        ],
+    )
+}
+
+fn update_setting_labels(
+    audio: Res<AudioSettings>,
+    trail: Res<TrailDisplay>,
+    vectors: Res<DecompositionOverlay>,
+    mut labels: Query<(&mut Text, &SettingLabel)>,
+) {
+    for (mut text, label) in &mut labels {
+        let value = match label {
+            SettingLabel::Volume => format!("{:.0}%", 100.0 * audio.volume),
+            SettingLabel::Muted => if audio.muted { "Muted" } else { "On" }.to_string(),
+            SettingLabel::Trail => format!("{:?}", *trail),
+            SettingLabel::Vectors => if vectors.0 { "Shown" } else { "Hidden" }.to_string(),
+        };
+        if text.0 != value {
+            text.0 = value;
+        }
+    }
+}
+
+const VOLUME_STEP: f32 = 0.1;
+
+fn lower_volume(_: Trigger<Pointer<Click>>, mut audio: ResMut<AudioSettings>) {
+    audio.volume = (audio.volume - VOLUME_STEP).max(0.0);
+}
+
+fn raise_volume(_: Trigger<Pointer<Click>>, mut audio: ResMut<AudioSettings>) {
+    audio.volume = (audio.volume + VOLUME_STEP).min(1.0);
+}
+
+fn toggle_mute(_: Trigger<Pointer<Click>>, mut audio: ResMut<AudioSettings>) {
+    audio.muted = !audio.muted;
+}
+
+fn previous_trail_display(_: Trigger<Pointer<Click>>, mut trail: ResMut<TrailDisplay>) {
+    *trail = trail.previous();
+}
+
+fn next_trail_display(_: Trigger<Pointer<Click>>, mut trail: ResMut<TrailDisplay>) {
+    *trail = trail.next();
+}
+
+fn toggle_vectors(_: Trigger<Pointer<Click>>, mut vectors: ResMut<DecompositionOverlay>) {
+    vectors.0 = !vectors.0;
+}
+
+fn go_back(_: Trigger<Pointer<Click>>, mut next_pause: ResMut<NextState<Pause>>) {
+    next_pause.set(Pause::Paused);
+}
@@ -0,0 +1,124 @@
+//! Audio cues tied to the motion model: footsteps at a rate proportional to
+//! speed, and a skid whenever a controller starts reversing.
+
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::{
+    AppSystems, MotionParameters,
+    asset_tracking::LoadResource,
+    audio::sound_effect,
+    player::{Controller, MotionRegime, MovementIntent, RegimeChanged},
+    side_scroll::movement::BasicMovementController,
+    top_down::movement::MovementController,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MotionAudioAssets>();
+    app.register_type::<Footsteps>();
+    app.load_resource::<MotionAudioAssets>();
+
+    app.add_systems(
+        Update,
+        (
+            play_footsteps::<BasicMovementController>,
+            play_footsteps::<MovementController>,
+            play_skids::<BasicMovementController>,
+            play_skids::<MovementController>,
+        )
+            .in_set(AppSystems::Update)
+            .run_if(resource_exists::<MotionAudioAssets>),
+    );
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct MotionAudioAssets {
+    #[dependency]
+    steps: Vec<Handle<AudioSource>>,
+    #[dependency]
+    skid: Handle<AudioSource>,
+}
+
+impl FromWorld for MotionAudioAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            steps: vec![
+                assets.load("audio/sound_effects/step1.wav"),
+                assets.load("audio/sound_effects/step2.wav"),
+                assets.load("audio/sound_effects/step3.wav"),
+                assets.load("audio/sound_effects/step4.wav"),
+            ],
+            skid: assets.load("audio/sound_effects/skid.wav"),
+        }
+    }
+}
+
+/// Plays a footstep every `stride` units travelled, so footsteps come faster
+/// the faster the controller moves.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+pub struct Footsteps {
+    pub stride: f32,
+    distance_since_step: f32,
+}
+
+impl Default for Footsteps {
+    fn default() -> Self {
+        Self {
+            stride: 60.0,
+            distance_since_step: 0.0,
+        }
+    }
+}
+
+fn play_footsteps<C: Controller>(
+    mut commands: Commands,
+    time: Res<Time>,
+    assets: Res<MotionAudioAssets>,
+    mut controllers: Query<(&C, &MotionRegime, &mut Footsteps)>,
+) {
+    for (controller, regime, mut footsteps) in &mut controllers {
+        // Sliding feet don't step.
+        if matches!(regime, MotionRegime::Stopping | MotionRegime::Reversing) {
+            footsteps.distance_since_step = 0.0;
+            continue;
+        }
+        footsteps.distance_since_step += controller.planar_velocity().length() * time.delta_secs();
+        if footsteps.distance_since_step < footsteps.stride {
+            continue;
+        }
+        footsteps.distance_since_step = 0.0;
+
+        let rng = &mut rand::thread_rng();
+        let step = assets.steps.choose(rng).unwrap().clone();
+        commands.spawn(sound_effect(step, 0.6));
+    }
+}
+
+fn play_skids<C: Controller>(
+    mut commands: Commands,
+    mut regime_events: EventReader<RegimeChanged>,
+    assets: Res<MotionAudioAssets>,
+    params: Res<MotionParameters>,
+    controllers: Query<(&C, &MovementIntent)>,
+) {
+    for event in regime_events.read() {
+        if event.to != MotionRegime::Reversing {
+            continue;
+        }
+        let Ok((controller, intent)) = controllers.get(event.entity) else {
+            continue;
+        };
+        // In the `alpha_rev` branch the controller decelerates at
+        // `(|target| + alpha_rev * speed) / t_acc`; scale that by its value
+        // for a full-speed reversal.
+        let speed = controller.planar_velocity().length();
+        let deceleration = intent.0.length() * params.max_speed + params.alpha_rev * speed;
+        let volume = deceleration / ((1.0 + params.alpha_rev) * params.max_speed);
+        if volume > 0.05 {
+            commands.spawn(sound_effect(assets.skid.clone(), volume.min(1.0)));
+        }
+    }
+}
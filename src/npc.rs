@@ -0,0 +1,181 @@
+//! Non-player characters, moved by the same controllers and tuning as the
+//! player but with their intent chosen by a [`Behaviour`] instead of input.
+
+use bevy::{color::palettes::tailwind, prelude::*};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    AppSystems, PausableSystems, player::MovementIntent,
+    side_scroll::movement::BasicMovementController,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Npc>();
+    app.register_type::<Behaviour>();
+    app.init_resource::<Crowd>();
+    app.init_resource::<NpcRng>();
+
+    app.add_systems(
+        Update,
+        choose_intent
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems),
+    );
+}
+
+/// Tints NPC sprites so that they stand apart from the player.
+pub(super) fn presentation_plugin(app: &mut App) {
+    app.add_systems(Update, tint_sprites);
+}
+
+/// Marks a character whose intent comes from its [`Behaviour`].
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Component)]
+pub struct Npc;
+
+/// How many NPCs to spawn alongside the player, cycling through seeking the
+/// player, patrolling, fleeing the player and wandering.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Crowd(pub usize);
+
+/// Where an NPC wants to go. Side-scroll NPCs only act on the horizontal part.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+pub enum Behaviour {
+    /// Head for `target`, letting go once within `radius` of it.
+    Seek { target: Entity, radius: f32 },
+    /// Run from `target` while it's within `radius`.
+    Flee { target: Entity, radius: f32 },
+    /// Head for each waypoint in turn, moving on once within `radius` of it.
+    Patrol {
+        waypoints: Vec<Vec2>,
+        next: usize,
+        radius: f32,
+    },
+    /// Stroll along a heading, in radians, that drifts by up to `turn_rate`
+    /// radians per second.
+    Wander { heading: f32, turn_rate: f32 },
+}
+
+/// The intent of a wandering NPC, as a fraction of full intent.
+const WANDER_INTENT: f32 = 0.5;
+
+impl Behaviour {
+    /// The behaviour of the `index`th member of a [`Crowd`] around `player`,
+    /// which starts at `home`.
+    pub fn crowd_member(index: usize, player: Entity, home: Vec2) -> Self {
+        match index % 4 {
+            0 => Behaviour::Seek {
+                target: player,
+                radius: 60.0,
+            },
+            1 => Behaviour::Patrol {
+                waypoints: vec![home - 300.0 * Vec2::X, home + 300.0 * Vec2::X],
+                next: 0,
+                radius: 20.0,
+            },
+            2 => Behaviour::Flee {
+                target: player,
+                radius: 300.0,
+            },
+            _ => Behaviour::Wander {
+                heading: index as f32,
+                turn_rate: 2.0,
+            },
+        }
+    }
+}
+
+/// The randomness behind wandering, seeded so that simulated runs repeat.
+#[derive(Resource, Debug)]
+pub struct NpcRng(pub StdRng);
+
+impl Default for NpcRng {
+    fn default() -> Self {
+        Self(StdRng::seed_from_u64(0))
+    }
+}
+
+/// Points the intent of each NPC where its behaviour wants to go. Positions
+/// are compared in the level's space, where players and NPCs are siblings.
+fn choose_intent(
+    time: Res<Time>,
+    mut rng: ResMut<NpcRng>,
+    mut npcs: Query<
+        (
+            &mut Behaviour,
+            &mut MovementIntent,
+            &Transform,
+            Has<BasicMovementController>,
+        ),
+        With<Npc>,
+    >,
+    targets: Query<&Transform>,
+) {
+    for (mut behaviour, mut intent, transform, side_scroll) in &mut npcs {
+        let position = transform.translation.xy();
+        // Side-scroll characters can only close the horizontal distance.
+        let offset_to = |point: Vec2| {
+            let offset = point - position;
+            if side_scroll {
+                Vec2::new(offset.x, 0.0)
+            } else {
+                offset
+            }
+        };
+        let target_position = |target: Entity| {
+            targets
+                .get(target)
+                .ok()
+                .map(|target| target.translation.xy())
+        };
+
+        let wanted = match &mut *behaviour {
+            Behaviour::Seek { target, radius } => target_position(*target)
+                .map(offset_to)
+                .filter(|offset| offset.length() > *radius)
+                .map_or(Vec2::ZERO, Vec2::normalize),
+            Behaviour::Flee { target, radius } => target_position(*target)
+                .map(offset_to)
+                .filter(|offset| offset.length() < *radius)
+                .map_or(Vec2::ZERO, |offset| -offset.normalize_or_zero()),
+            Behaviour::Patrol {
+                waypoints,
+                next,
+                radius,
+            } => {
+                if waypoints.is_empty() {
+                    Vec2::ZERO
+                } else {
+                    *next %= waypoints.len();
+                    if offset_to(waypoints[*next]).length() <= *radius {
+                        *next = (*next + 1) % waypoints.len();
+                    }
+                    offset_to(waypoints[*next]).normalize_or_zero()
+                }
+            }
+            Behaviour::Wander { heading, turn_rate } => {
+                *heading += rng.0.gen_range(-1.0..=1.0) * *turn_rate * time.delta_secs();
+                let direction = Vec2::from_angle(*heading);
+                let direction = if side_scroll {
+                    Vec2::new(direction.x, 0.0).normalize_or_zero()
+                } else {
+                    direction
+                };
+                WANDER_INTENT * direction
+            }
+        };
+        intent.0 = wanted;
+    }
+}
+
+fn tint_sprites(
+    mut sprites: Query<(&ChildOf, &mut Sprite), Added<Sprite>>,
+    npcs: Query<(), With<Npc>>,
+) {
+    for (child_of, mut sprite) in &mut sprites {
+        if npcs.contains(child_of.parent()) {
+            sprite.color = tailwind::LIME_300.into();
+        }
+    }
+}
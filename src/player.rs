@@ -5,6 +5,9 @@ use bevy::prelude::*;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<Player>();
+    app.register_type::<MotionRegime>();
+    app.add_event::<RegimeChanged>();
+    app.init_resource::<PlayerCamera>();
     // app.register_type::<ScreenWrap>();
 
     // app.add_systems(Update, apply_screen_wrap.in_set(AppSystems::Update));
@@ -14,10 +17,56 @@ pub(super) fn plugin(app: &mut App) {
 #[reflect(Component)]
 pub struct Player;
 
+/// Where a character is trying to go. Side-scroll characters move along the
+/// ground by `x` and jump while `y` is positive.
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
 pub struct MovementIntent(pub Vec2);
 
+/// Read access shared by the side-scroll and top-down movement controllers.
+pub trait Controller: Component {
+    /// Current velocity in the controller's plane of motion. Side-scroll
+    /// controllers report their velocity along the ground as `x`.
+    fn planar_velocity(&self) -> Vec2;
+}
+
+/// Which damping branch a movement controller took on its most recent update.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Component)]
+pub enum MotionRegime {
+    /// Moving along the intent, damped with `alpha = 1`.
+    Accelerating,
+    /// No intent, damped with `alpha_stop`.
+    #[default]
+    Stopping,
+    /// Moving against the intent, damped with `alpha_rev`.
+    Reversing,
+    /// Moving mostly across the intent, dominated by `alpha_turn`.
+    Turning,
+}
+
+/// Sent when a movement controller switches [`MotionRegime`], for example when
+/// it starts reversing. Useful for driving animation and audio.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RegimeChanged {
+    pub entity: Entity,
+    pub from: MotionRegime,
+    pub to: MotionRegime,
+}
+
+/// Moves `regime` to `to`, sending a [`RegimeChanged`] event if that is a change.
+pub(crate) fn update_regime(
+    entity: Entity,
+    mut regime: Mut<MotionRegime>,
+    to: MotionRegime,
+    events: &mut EventWriter<RegimeChanged>,
+) {
+    let from = *regime;
+    if regime.set_if_neq(to) {
+        events.write(RegimeChanged { entity, from, to });
+    }
+}
+
 // #[derive(Component, Reflect, Default)]
 // #[reflect(Component)]
 // pub struct ScreenWrap;
@@ -40,9 +89,21 @@ pub struct MovementIntent(pub Vec2);
 // }
 
 #[derive(Component)]
-#[relationship_target(relationship = CameraOf)]
+#[relationship_target(relationship = CameraOf, linked_spawn)]
 pub struct TrackingCameras(Vec<Entity>);
 
+/// Makes a camera follow a player. Add this to your own camera when the
+/// level is spawned without one.
 #[derive(Component)]
 #[relationship(relationship_target = TrackingCameras)]
-pub struct CameraOf(Entity);
+pub struct CameraOf(pub Entity);
+
+/// Whether each spawned player gets a camera of its own to follow it.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerCamera(pub bool);
+
+impl Default for PlayerCamera {
+    fn default() -> Self {
+        Self(true)
+    }
+}
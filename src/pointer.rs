@@ -0,0 +1,273 @@
+//! Mouse and touch control: click or tap the world to walk there, or drag an
+//! on-screen joystick that appears once the screen is touched.
+
+use bevy::{color::palettes::tailwind, prelude::*, ui::Val::*, window::PrimaryWindow};
+
+use crate::{
+    AppSystems, PausableSystems,
+    input::{Action, KeyBindings},
+    player::{MovementIntent, Player, TrackingCameras},
+    screens::Screen,
+    side_scroll::{self, movement::BasicMovementController},
+    top_down,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PointerTarget>();
+    app.init_resource::<Joystick>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_joystick);
+    app.add_systems(
+        Update,
+        (
+            (track_joystick, set_pointer_target, steer_players)
+                .chain()
+                .in_set(AppSystems::RecordInput)
+                .in_set(PausableSystems)
+                .after(side_scroll::player::record_player_directional_input)
+                .after(top_down::player::record_player_directional_input),
+            (draw_pointer_targets, update_joystick).in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Where a player is walking to after a click or tap, until it arrives or a
+/// movement key is pressed.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct PointerTarget(pub Vec2);
+
+/// Distance from a pointer target within which the intent eases off.
+const SLOWDOWN_RADIUS: f32 = 100.0;
+
+/// Distance from a pointer target that counts as having arrived.
+const ARRIVAL_RADIUS: f32 = 5.0;
+
+/// Radius of the joystick's base, in logical pixels.
+const JOYSTICK_RADIUS: f32 = 70.0;
+
+/// Gap between the joystick's base and the bottom left corner of the window.
+const JOYSTICK_MARGIN: f32 = 40.0;
+
+/// How far up the joystick's knob has to be pushed, as a fraction of its
+/// radius, for side-scroll players to jump.
+const JOYSTICK_JUMP: f32 = 0.5;
+
+/// The state of the on-screen joystick.
+#[derive(Resource, Debug, Default)]
+pub struct Joystick {
+    /// Whether a touch has been seen, so that the joystick is worth showing.
+    pub visible: bool,
+    /// The touch holding the knob, if any.
+    touch: Option<u64>,
+    /// The knob's offset from the centre of the base, as a fraction of its
+    /// radius with `y` up.
+    pub intent: Option<Vec2>,
+}
+
+#[derive(Component)]
+struct JoystickBase;
+
+#[derive(Component)]
+struct JoystickKnob;
+
+fn spawn_joystick(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Joystick"),
+        JoystickBase,
+        Node {
+            position_type: PositionType::Absolute,
+            left: Px(JOYSTICK_MARGIN),
+            bottom: Px(JOYSTICK_MARGIN),
+            width: Px(2.0 * JOYSTICK_RADIUS),
+            height: Px(2.0 * JOYSTICK_RADIUS),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BorderRadius::MAX,
+        BackgroundColor(Color::WHITE.with_alpha(0.15)),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![(
+            Name::new("Joystick Knob"),
+            JoystickKnob,
+            Node {
+                width: Px(JOYSTICK_RADIUS),
+                height: Px(JOYSTICK_RADIUS),
+                ..default()
+            },
+            BorderRadius::MAX,
+            BackgroundColor(Color::WHITE.with_alpha(0.4)),
+            Pickable::IGNORE,
+        )],
+    ));
+}
+
+/// The centre of the joystick's base, in logical pixels from the top left.
+fn joystick_centre(window: &Window) -> Vec2 {
+    Vec2::new(
+        JOYSTICK_MARGIN + JOYSTICK_RADIUS,
+        window.height() - JOYSTICK_MARGIN - JOYSTICK_RADIUS,
+    )
+}
+
+fn track_joystick(
+    touches: Res<Touches>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut joystick: ResMut<Joystick>,
+) {
+    let centre = joystick_centre(&window);
+    for touch in touches.iter_just_pressed() {
+        joystick.visible = true;
+        if joystick.touch.is_none() && touch.position().distance(centre) <= JOYSTICK_RADIUS {
+            joystick.touch = Some(touch.id());
+        }
+    }
+    joystick.intent = joystick
+        .touch
+        .and_then(|id| touches.get_pressed(id))
+        .map(|touch| {
+            let offset = (touch.position() - centre) / JOYSTICK_RADIUS;
+            Vec2::new(offset.x, -offset.y).clamp_length_max(1.0)
+        });
+    if joystick.intent.is_none() {
+        joystick.touch = None;
+    }
+}
+
+/// Points each player at the place in the world that was clicked or tapped,
+/// unless the tap was on the joystick.
+fn set_pointer_target(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    joystick: Res<Joystick>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    players: Query<(Entity, Option<&TrackingCameras>), With<Player>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+) {
+    let joystick_touch = joystick.touch;
+    let pressed = touches
+        .iter_just_pressed()
+        .filter(|touch| Some(touch.id()) != joystick_touch)
+        .map(|touch| touch.position())
+        .chain(
+            mouse
+                .just_pressed(MouseButton::Left)
+                .then(|| window.cursor_position())
+                .flatten(),
+        )
+        .last();
+    let Some(screen_position) = pressed else {
+        return;
+    };
+
+    for (player, its_cameras) in &players {
+        // Use the player's own camera, or the only camera if it has none.
+        let camera = match its_cameras.and_then(|its_cameras| its_cameras.iter().next()) {
+            Some(camera) => cameras.get(camera).ok(),
+            None => cameras.single().ok(),
+        };
+        let Some((camera, camera_transform)) = camera else {
+            continue;
+        };
+        if let Ok(target) = camera.viewport_to_world_2d(camera_transform, screen_position) {
+            commands.entity(player).insert(PointerTarget(target));
+        }
+    }
+}
+
+/// Overrides the keyboard intent with the joystick's or one towards the
+/// pointer target, easing off near the target. Pressing a movement key drops
+/// the target. Pushing the joystick up makes side-scroll players jump.
+fn steer_players(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    joystick: Res<Joystick>,
+    mut players: Query<
+        (
+            Entity,
+            &mut MovementIntent,
+            &Transform,
+            Option<&PointerTarget>,
+            Has<BasicMovementController>,
+        ),
+        With<Player>,
+    >,
+) {
+    let keyboard = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::MoveUp,
+        Action::MoveDown,
+    ]
+    .into_iter()
+    .any(|action| bindings.pressed(action, &input));
+
+    for (player, mut intent, transform, target, side_scroll) in &mut players {
+        // Side-scroll players only move horizontally, but keep any jump.
+        let jump = if side_scroll { intent.0.y } else { 0.0 };
+        let planar = |vector: Vec2| {
+            if side_scroll {
+                Vec2::new(vector.x, 0.0)
+            } else {
+                vector
+            }
+        };
+
+        if keyboard || joystick.intent.is_some() {
+            if target.is_some() {
+                commands.entity(player).remove::<PointerTarget>();
+            }
+            if let Some(joystick_intent) = joystick.intent.filter(|_| !keyboard) {
+                let jump = if side_scroll && joystick_intent.y > JOYSTICK_JUMP {
+                    1.0
+                } else {
+                    jump
+                };
+                intent.0 = planar(joystick_intent) + jump * Vec2::Y;
+            }
+            continue;
+        }
+        let Some(PointerTarget(target)) = target else {
+            continue;
+        };
+        let offset = planar(*target - transform.translation.xy());
+        if offset.length() <= ARRIVAL_RADIUS {
+            commands.entity(player).remove::<PointerTarget>();
+            intent.0 = jump * Vec2::Y;
+            continue;
+        }
+        intent.0 =
+            offset.normalize() * (offset.length() / SLOWDOWN_RADIUS).min(1.0) + jump * Vec2::Y;
+    }
+}
+
+fn draw_pointer_targets(targets: Query<&PointerTarget, With<Player>>, mut gizmos: Gizmos) {
+    for PointerTarget(target) in &targets {
+        gizmos.circle_2d(*target, ARRIVAL_RADIUS * 2.0, tailwind::SKY_300);
+    }
+}
+
+fn update_joystick(
+    joystick: Res<Joystick>,
+    mut base: Query<&mut Visibility, With<JoystickBase>>,
+    mut knob: Query<&mut Node, With<JoystickKnob>>,
+) {
+    for mut visibility in &mut base {
+        visibility.set_if_neq(if joystick.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+    }
+    let offset = joystick.intent.unwrap_or_default() * JOYSTICK_RADIUS;
+    for mut node in &mut knob {
+        node.left = Px(offset.x);
+        node.top = Px(-offset.y);
+    }
+}
@@ -0,0 +1,175 @@
+//! Predict where a controller will go by running the same update as
+//! `apply_movement` ahead of time, and draw where each player would stop.
+
+use bevy::{color::palettes::tailwind, prelude::*};
+
+use crate::{
+    MotionParameters, PlayMode,
+    input::{Action, action_just_pressed},
+    player::{MotionRegime, Player},
+    side_scroll::{
+        self,
+        level::{GroundProfile, PositionAlongGround},
+        movement::BasicMovementController,
+    },
+    top_down::{
+        self,
+        movement::MovementController,
+        surface::{self, Surface},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<StopPointGizmo>();
+    app.init_resource::<StopPointGizmo>();
+
+    app.add_systems(
+        Update,
+        toggle_stop_point.run_if(action_just_pressed(Action::ToggleStopPoint)),
+    );
+    // Draw after transform propagation so that every mode's ground mapping
+    // has already placed the player for this frame.
+    app.add_systems(
+        PostUpdate,
+        (
+            draw_side_scroll_stop_point.run_if(in_state(PlayMode::SideScroll)),
+            draw_top_down_stop_point.run_if(in_state(PlayMode::TopDown)),
+        )
+            .after(TransformSystem::TransformPropagate)
+            .run_if(|gizmo: Res<StopPointGizmo>| gizmo.0),
+    );
+}
+
+/// The state of a controller after one predicted step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredictedState {
+    /// Seconds after the start of the prediction.
+    pub time: f32,
+    /// Side-scroll predictions report the position along the ground as `x`.
+    pub position: Vec2,
+    /// Side-scroll predictions report the velocity along the ground as `x`.
+    pub velocity: Vec2,
+    pub regime: MotionRegime,
+}
+
+/// Steps a top-down controller once per intent, `dt` seconds at a time,
+/// exactly as [`top_down::movement::apply_movement`] would on flat ground.
+pub fn predict_top_down(
+    mut position: Vec2,
+    mut velocity: Vec2,
+    intents: impl IntoIterator<Item = Vec2>,
+    dt: f32,
+    params: &MotionParameters,
+) -> Vec<PredictedState> {
+    let mut time = 0.0;
+    intents
+        .into_iter()
+        .map(|intent| {
+            let regime;
+            (velocity, regime) = top_down::movement::step(velocity, intent, dt, params);
+            position += velocity * dt;
+            time += dt;
+            PredictedState {
+                time,
+                position,
+                velocity,
+                regime,
+            }
+        })
+        .collect()
+}
+
+/// Steps a side-scroll controller once per horizontal intent, `dt` seconds at
+/// a time, exactly as [`side_scroll::movement::apply_movement`] would.
+/// `gravity_along_ground` gives the component of gravity along the ground at
+/// a position along it, which is zero everywhere on flat ground.
+pub fn predict_side_scroll(
+    mut position: f32,
+    mut velocity: f32,
+    intents: impl IntoIterator<Item = f32>,
+    gravity_along_ground: impl Fn(f32) -> f32,
+    dt: f32,
+    params: &MotionParameters,
+) -> Vec<PredictedState> {
+    let mut time = 0.0;
+    intents
+        .into_iter()
+        .map(|intent| {
+            let regime;
+            (velocity, regime) = side_scroll::movement::step(
+                velocity,
+                intent,
+                gravity_along_ground(position),
+                dt,
+                params,
+            );
+            position += velocity * dt;
+            time += dt;
+            PredictedState {
+                time,
+                position: Vec2::new(position, 0.0),
+                velocity: Vec2::new(velocity, 0.0),
+                regime,
+            }
+        })
+        .collect()
+}
+
+/// Whether each player's predicted stop point is drawn, toggled with
+/// [`Action::ToggleStopPoint`].
+#[derive(Resource, Reflect, Debug, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct StopPointGizmo(pub bool);
+
+fn toggle_stop_point(mut gizmo: ResMut<StopPointGizmo>) {
+    gizmo.0 = !gizmo.0;
+}
+
+/// Radius of the circle marking a stop point.
+const STOP_POINT_RADIUS: f32 = 12.0;
+
+/// Marks where each side-scroll player would come to rest if it let go now,
+/// measured along the ground but ignoring the pull of its slopes.
+fn draw_side_scroll_stop_point(
+    players: Query<
+        (
+            &BasicMovementController,
+            &PositionAlongGround,
+            &GlobalTransform,
+        ),
+        With<Player>,
+    >,
+    params: Res<MotionParameters>,
+    ground: Res<side_scroll::Ground>,
+    profile: Res<GroundProfile>,
+    mut gizmos: Gizmos,
+) {
+    for (controller, arc_pos, transform) in &players {
+        let distance = params.stopping_distance(controller.velocity());
+        let (stop_point, _) = ground.placement(&profile, arc_pos.0 + distance);
+        draw_stop_point(&mut gizmos, transform.translation().xy(), stop_point);
+    }
+}
+
+/// Marks where each top-down player would come to rest if it let go now.
+fn draw_top_down_stop_point(
+    players: Query<(&MovementController, &GlobalTransform), With<Player>>,
+    surfaces: Query<(&Surface, &Transform)>,
+    params: Res<MotionParameters>,
+    mut gizmos: Gizmos,
+) {
+    for (controller, transform) in &players {
+        let position = transform.translation().xy();
+        let velocity = controller.velocity();
+        let params = surface::tuning_at(&surfaces, &params, position);
+        let stop_point =
+            position + velocity.normalize_or_zero() * params.stopping_distance(velocity.length());
+        draw_stop_point(&mut gizmos, position, stop_point);
+    }
+}
+
+fn draw_stop_point(gizmos: &mut Gizmos, position: Vec2, stop_point: Vec2) {
+    gizmos.line_2d(position, stop_point, tailwind::AMBER_400.with_alpha(0.5));
+    gizmos.circle_2d(stop_point, STOP_POINT_RADIUS, tailwind::AMBER_400);
+    gizmos.cross_2d(stop_point, STOP_POINT_RADIUS * 0.5, tailwind::AMBER_400);
+}
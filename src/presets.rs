@@ -0,0 +1,230 @@
+//! Named tunings loaded from a data file, cycled in-game and shown on screen.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+    ui::Val::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MotionParameters, PausableSystems,
+    asset_tracking::LoadResource,
+    input::{Action, action_just_pressed},
+    screens::Screen,
+    theme::prelude::*,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Presets>();
+    app.init_asset_loader::<PresetsLoader>();
+    app.register_type::<PresetAssets>();
+    app.load_resource::<PresetAssets>();
+    app.init_resource::<SelectedPreset>();
+
+    app.add_systems(OnExit(Screen::Loading), apply_requested_preset);
+    app.add_systems(OnEnter(Screen::Gameplay), spawn_preset_label);
+    app.add_systems(
+        Update,
+        (
+            cycle_preset
+                .in_set(PausableSystems)
+                .run_if(action_just_pressed(Action::CyclePreset)),
+            update_preset_label,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(Last, forget_edited_preset);
+}
+
+/// A tuning with a name, as listed in `tunings.presets.ron`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Preset {
+    pub name: String,
+    pub params: MotionParameters,
+}
+
+/// The list of named tunings in a `.presets.ron` file.
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(transparent)]
+pub struct Presets(pub Vec<Preset>);
+
+impl Presets {
+    pub fn get(&self, name: &str) -> Option<&Preset> {
+        self.position(name).map(|index| &self.0[index])
+    }
+
+    /// The index of the preset called `name`, ignoring case.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The index of the preset `step` places after `current`, wrapping
+    /// around. Without a current preset, stepping forward starts from the
+    /// first and stepping back from the last.
+    pub fn step(&self, current: Option<usize>, step: isize) -> Option<usize> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let index = match current {
+            Some(index) => index as isize + step,
+            None if step > 0 => step - 1,
+            None => step,
+        };
+        Some(index.rem_euclid(self.0.len() as isize) as usize)
+    }
+}
+
+#[derive(Default, TypePath)]
+struct PresetsLoader;
+
+impl AssetLoader for PresetsLoader {
+    type Asset = Presets;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Presets, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["presets.ron"]
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct PresetAssets {
+    #[dependency]
+    presets: Handle<Presets>,
+}
+
+impl FromWorld for PresetAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            presets: assets.load("tunings.presets.ron"),
+        }
+    }
+}
+
+impl PresetAssets {
+    pub fn handle(&self) -> &Handle<Presets> {
+        &self.presets
+    }
+}
+
+/// The index of the preset last picked, until the tuning is edited some
+/// other way.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SelectedPreset(pub Option<usize>);
+
+/// The name of a preset to switch to once presets have loaded, taking
+/// priority over both the launch arguments and saved settings.
+#[derive(Resource, Debug, Clone)]
+pub struct RequestedPreset(pub String);
+
+pub(crate) fn apply_requested_preset(
+    requested: Option<Res<RequestedPreset>>,
+    preset_assets: Res<PresetAssets>,
+    presets: Res<Assets<Presets>>,
+    params: ResMut<MotionParameters>,
+    selected: ResMut<SelectedPreset>,
+) {
+    let Some(requested) = requested else {
+        return;
+    };
+    let Some(presets) = presets.get(&preset_assets.presets) else {
+        return;
+    };
+    match presets.position(&requested.0) {
+        Some(index) => select_preset(presets, index, params, selected),
+        None => warn!("No tuning preset named {:?}", requested.0),
+    }
+}
+
+/// Switches to the tuning of the preset at `index`.
+pub fn select_preset(
+    presets: &Presets,
+    index: usize,
+    mut params: ResMut<MotionParameters>,
+    mut selected: ResMut<SelectedPreset>,
+) {
+    *params = presets.0[index].params;
+    selected.0 = Some(index);
+}
+
+fn cycle_preset(
+    preset_assets: Res<PresetAssets>,
+    presets: Res<Assets<Presets>>,
+    params: ResMut<MotionParameters>,
+    selected: ResMut<SelectedPreset>,
+) {
+    let Some(presets) = presets.get(&preset_assets.presets) else {
+        return;
+    };
+    if let Some(index) = presets.step(selected.0, 1) {
+        select_preset(presets, index, params, selected);
+    }
+}
+
+/// Forgets the selected preset once the tuning changes without a preset
+/// being picked, as it no longer describes the tuning.
+fn forget_edited_preset(params: Res<MotionParameters>, mut selected: ResMut<SelectedPreset>) {
+    if params.is_changed() && !selected.is_changed() && selected.0.is_some() {
+        selected.0 = None;
+    }
+}
+
+#[derive(Component, Debug)]
+struct PresetLabel;
+
+fn spawn_preset_label(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Preset Label"),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Px(20.0),
+            top: Px(20.0),
+            ..default()
+        },
+        Pickable::IGNORE,
+        StateScoped(Screen::Gameplay),
+        children![(widget::label(""), PresetLabel)],
+    ));
+}
+
+/// The name to show for the `selected` preset.
+pub fn preset_name(presets: &Presets, selected: SelectedPreset) -> String {
+    selected
+        .0
+        .and_then(|index| presets.0.get(index))
+        .map_or_else(|| "Custom".to_string(), |preset| preset.name.clone())
+}
+
+fn update_preset_label(
+    preset_assets: Res<PresetAssets>,
+    presets: Res<Assets<Presets>>,
+    selected: Res<SelectedPreset>,
+    mut labels: Query<&mut Text, With<PresetLabel>>,
+) {
+    let Some(presets) = presets.get(&preset_assets.presets) else {
+        return;
+    };
+    let value = format!("Tuning: {}", preset_name(presets, *selected));
+    for mut text in &mut labels {
+        if text.0 != value {
+            text.0 = value.clone();
+        }
+    }
+}
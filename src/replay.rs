@@ -0,0 +1,166 @@
+//! Record the keys held on each gameplay frame to a file, and play them back
+//! later in place of the keyboard.
+//!
+//! Only frames on the gameplay screen are recorded, so that a replay lines up
+//! however long loading takes. The tuning and ground the run started with are
+//! recorded too and restored on replaying, so that a replay doesn't depend on
+//! saved settings. Replays are exact when both runs use the same fixed
+//! timestep.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{input::InputSystem, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{LevelGround, MotionParameters, PlayMode, screens::Screen, side_scroll, top_down};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PreUpdate,
+        (
+            replay_input.run_if(resource_exists::<InputReplay>),
+            record_input.run_if(resource_exists::<InputRecorder>),
+        )
+            .chain()
+            .after(InputSystem)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        restore_recorded_start.run_if(resource_exists::<InputReplay>),
+    );
+    app.add_systems(
+        Last,
+        save_recording.run_if(resource_exists::<InputRecorder>.and(on_event::<AppExit>)),
+    );
+}
+
+/// The keys held on each frame of a recorded run.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InputRecording {
+    /// The fixed timestep the run was recorded with, in seconds, if any.
+    pub timestep: Option<f64>,
+    /// How the run started, if it was recorded.
+    #[serde(default)]
+    pub start: Option<RecordedStart>,
+    pub frames: Vec<Vec<KeyCode>>,
+}
+
+/// The tuning and ground a recorded run started with.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RecordedStart {
+    pub params: MotionParameters,
+    /// The ground, which also says which mode the run was in.
+    pub ground: LevelGround,
+    pub terrain: side_scroll::EndlessTerrain,
+}
+
+impl InputRecording {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|error| error.to_string())?;
+        ron::from_str(&text).map_err(|error| error.to_string())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text =
+            ron::ser::to_string_pretty(self, default()).map_err(|error| error.to_string())?;
+        fs::write(path, text).map_err(|error| error.to_string())
+    }
+}
+
+/// Records held keys into a file that is written when the app exits.
+#[derive(Resource, Debug)]
+pub struct InputRecorder {
+    pub path: PathBuf,
+    pub recording: InputRecording,
+}
+
+/// Plays back a recording, exiting the app when it runs out.
+#[derive(Resource, Debug)]
+pub struct InputReplay {
+    pub recording: InputRecording,
+    pub frame: usize,
+}
+
+fn record_input(
+    input: Res<ButtonInput<KeyCode>>,
+    mut recorder: ResMut<InputRecorder>,
+    mode: Res<State<PlayMode>>,
+    params: Res<MotionParameters>,
+    side_scroll_ground: Res<side_scroll::Ground>,
+    top_down_ground: Res<top_down::Ground>,
+    terrain: Res<side_scroll::EndlessTerrain>,
+) {
+    if recorder.recording.frames.is_empty() {
+        recorder.recording.start = Some(RecordedStart {
+            params: *params,
+            ground: match mode.get() {
+                PlayMode::SideScroll => LevelGround::SideScroll(*side_scroll_ground),
+                PlayMode::TopDown => LevelGround::TopDown(*top_down_ground),
+            },
+            terrain: *terrain,
+        });
+    }
+    let held = input.get_pressed().copied().collect();
+    recorder.recording.frames.push(held);
+}
+
+fn replay_input(
+    mut input: ResMut<ButtonInput<KeyCode>>,
+    mut replay: ResMut<InputReplay>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let Some(held) = replay.recording.frames.get(replay.frame) else {
+        exit.write(AppExit::Success);
+        return;
+    };
+    let released: Vec<_> = input
+        .get_pressed()
+        .filter(|key| !held.contains(key))
+        .copied()
+        .collect();
+    for key in released {
+        input.release(key);
+    }
+    for &key in held {
+        input.press(key);
+    }
+    replay.frame += 1;
+}
+
+/// Puts the tuning and ground back the way they were when the recording
+/// started, whatever the launch or saved settings chose.
+fn restore_recorded_start(
+    replay: Res<InputReplay>,
+    mut next_mode: ResMut<NextState<PlayMode>>,
+    mut params: ResMut<MotionParameters>,
+    mut side_scroll_ground: ResMut<side_scroll::Ground>,
+    mut top_down_ground: ResMut<top_down::Ground>,
+    mut terrain: ResMut<side_scroll::EndlessTerrain>,
+) {
+    let Some(start) = replay.recording.start else {
+        return;
+    };
+    *params = start.params;
+    *terrain = start.terrain;
+    match start.ground {
+        LevelGround::SideScroll(ground) => {
+            *side_scroll_ground = ground;
+            next_mode.set(PlayMode::SideScroll);
+        }
+        LevelGround::TopDown(ground) => {
+            *top_down_ground = ground;
+            next_mode.set(PlayMode::TopDown);
+        }
+    }
+}
+
+fn save_recording(recorder: Res<InputRecorder>) {
+    match recorder.recording.save(&recorder.path) {
+        Ok(()) => info!("Saved input recording to {}", recorder.path.display()),
+        Err(error) => warn!("Failed to save input recording: {error}"),
+    }
+}
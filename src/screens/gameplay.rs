@@ -2,8 +2,78 @@
 
 use bevy::prelude::*;
 
-use crate::{level::spawn_level, screens::Screen};
+use crate::{
+    PausableSystems, PlayMode,
+    input::{Action, action_just_pressed},
+    level::{LevelRoot, spawn_level},
+    screens::Screen,
+};
 
 pub(super) fn plugin(app: &mut App) {
+    app.add_sub_state::<Pause>();
+
     app.add_systems(OnEnter(Screen::Gameplay), spawn_level);
+
+    // Respawn the level when the play mode is switched mid-game.
+    for mode in [PlayMode::SideScroll, PlayMode::TopDown] {
+        app.add_systems(
+            OnEnter(mode),
+            spawn_level.run_if(in_state(Screen::Gameplay)),
+        );
+    }
+
+    app.add_systems(
+        Update,
+        (
+            switch_play_mode
+                .in_set(PausableSystems)
+                .run_if(action_just_pressed(Action::SwitchMode)),
+            toggle_pause.run_if(action_just_pressed(Action::Pause)),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+
+    // Freeze the simulation clock while any pause menu is open.
+    app.add_systems(OnExit(Pause::Running), pause_time);
+    app.add_systems(OnEnter(Pause::Running), unpause_time);
+    app.add_systems(OnExit(Screen::Gameplay), unpause_time);
+}
+
+/// Whether gameplay is running, and if not, which pause menu is open.
+#[derive(SubStates, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[source(Screen = Screen::Gameplay)]
+#[states(scoped_entities)]
+pub enum Pause {
+    #[default]
+    Running,
+    Paused,
+    Settings,
+}
+
+fn switch_play_mode(mode: Res<State<PlayMode>>, mut next_mode: ResMut<NextState<PlayMode>>) {
+    next_mode.set(mode.get().toggled());
+}
+
+fn toggle_pause(pause: Res<State<Pause>>, mut next_pause: ResMut<NextState<Pause>>) {
+    next_pause.set(match pause.get() {
+        Pause::Running => Pause::Paused,
+        Pause::Paused => Pause::Running,
+        Pause::Settings => Pause::Paused,
+    });
+}
+
+fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+/// Despawn the level and spawn it again, putting the player back at the start.
+pub fn reset_level(mut commands: Commands, levels: Query<Entity, With<LevelRoot>>) {
+    for level in &levels {
+        commands.entity(level).despawn();
+    }
+    commands.run_system_cached(spawn_level);
 }
@@ -2,13 +2,18 @@
 
 mod gameplay;
 mod loading;
+mod title;
 
 use bevy::prelude::*;
 
+pub use gameplay::{Pause, reset_level};
+
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
 
-    app.add_plugins((gameplay::plugin, loading::plugin));
+    app.init_resource::<AfterLoading>();
+
+    app.add_plugins((gameplay::plugin, loading::plugin, title::plugin));
 }
 
 /// The game's main screen states.
@@ -17,5 +22,16 @@ pub(super) fn plugin(app: &mut App) {
 pub enum Screen {
     #[default]
     Loading,
+    Title,
     Gameplay,
 }
+
+/// The screen to enter once loading has finished.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct AfterLoading(pub Screen);
+
+impl Default for AfterLoading {
+    fn default() -> Self {
+        Self(Screen::Title)
+    }
+}
@@ -0,0 +1,171 @@
+//! The title screen, where the play mode, ground and tuning are chosen.
+
+use bevy::{prelude::*, ui::Val::*};
+
+use crate::{
+    MotionParameters, PlayMode,
+    presets::{PresetAssets, Presets, SelectedPreset, preset_name, select_preset},
+    screens::Screen,
+    side_scroll,
+    theme::prelude::*,
+    top_down,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Title), spawn_title_screen);
+    app.add_systems(Update, update_option_labels.run_if(in_state(Screen::Title)));
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum TitleOption {
+    Mode,
+    Ground,
+    Preset,
+}
+
+fn spawn_title_screen(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Title Camera"),
+        Camera2d,
+        StateScoped(Screen::Title),
+    ));
+    commands.spawn((
+        widget::ui_root("Title Screen"),
+        StateScoped(Screen::Title),
+        children![
+            widget::header("Character Motion"),
+            options_grid(),
+            widget::button("Play", enter_gameplay_screen),
+        ],
+    ));
+}
+
+fn options_grid() -> impl Bundle {
+    (
+        Name::new("Options Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: Px(10.0),
+            column_gap: Px(30.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 300.0),
+            ..default()
+        },
+        children![
+            widget::grid_label("Mode"),
+            widget::stepper(
+                (widget::label(""), TitleOption::Mode),
+                cycle_mode,
+                cycle_mode
+            ),
+            widget::grid_label("Ground"),
+            widget::stepper(
+                (widget::label(""), TitleOption::Ground),
+                previous_ground,
+                next_ground
+            ),
+            widget::grid_label("Tuning"),
+            widget::stepper(
+                (widget::label(""), TitleOption::Preset),
+                previous_preset,
+                next_preset
+            ),
+        ],
+    )
+}
+
+fn update_option_labels(
+    mode: Res<State<PlayMode>>,
+    side_scroll_ground: Res<side_scroll::Ground>,
+    top_down_ground: Res<top_down::Ground>,
+    preset_assets: Res<PresetAssets>,
+    presets: Res<Assets<Presets>>,
+    selected: Res<SelectedPreset>,
+    mut labels: Query<(&mut Text, &TitleOption)>,
+) {
+    for (mut text, option) in &mut labels {
+        let value = match option {
+            TitleOption::Mode => mode.get().to_string(),
+            TitleOption::Ground => match mode.get() {
+                PlayMode::SideScroll => side_scroll_ground.to_string(),
+                PlayMode::TopDown => top_down_ground.to_string(),
+            },
+            TitleOption::Preset => presets
+                .get(preset_assets.handle())
+                .map_or_else(String::new, |presets| preset_name(presets, *selected)),
+        };
+        if text.0 != value {
+            text.0 = value;
+        }
+    }
+}
+
+fn cycle_mode(
+    _: Trigger<Pointer<Click>>,
+    mode: Res<State<PlayMode>>,
+    mut next_mode: ResMut<NextState<PlayMode>>,
+) {
+    next_mode.set(mode.get().toggled());
+}
+
+fn previous_ground(
+    _: Trigger<Pointer<Click>>,
+    mode: Res<State<PlayMode>>,
+    mut side_scroll_ground: ResMut<side_scroll::Ground>,
+    mut top_down_ground: ResMut<top_down::Ground>,
+) {
+    match mode.get() {
+        PlayMode::SideScroll => *side_scroll_ground = side_scroll_ground.previous(),
+        PlayMode::TopDown => *top_down_ground = top_down_ground.previous(),
+    }
+}
+
+fn next_ground(
+    _: Trigger<Pointer<Click>>,
+    mode: Res<State<PlayMode>>,
+    mut side_scroll_ground: ResMut<side_scroll::Ground>,
+    mut top_down_ground: ResMut<top_down::Ground>,
+) {
+    match mode.get() {
+        PlayMode::SideScroll => *side_scroll_ground = side_scroll_ground.next(),
+        PlayMode::TopDown => *top_down_ground = top_down_ground.next(),
+    }
+}
+
+fn previous_preset(
+    _: Trigger<Pointer<Click>>,
+    preset_assets: Res<PresetAssets>,
+    presets: Res<Assets<Presets>>,
+    params: ResMut<MotionParameters>,
+    selected: ResMut<SelectedPreset>,
+) {
+    step_preset(&preset_assets, &presets, params, selected, -1);
+}
+
+fn next_preset(
+    _: Trigger<Pointer<Click>>,
+    preset_assets: Res<PresetAssets>,
+    presets: Res<Assets<Presets>>,
+    params: ResMut<MotionParameters>,
+    selected: ResMut<SelectedPreset>,
+) {
+    step_preset(&preset_assets, &presets, params, selected, 1);
+}
+
+fn step_preset(
+    preset_assets: &PresetAssets,
+    presets: &Assets<Presets>,
+    params: ResMut<MotionParameters>,
+    selected: ResMut<SelectedPreset>,
+    step: isize,
+) {
+    let Some(presets) = presets.get(preset_assets.handle()) else {
+        return;
+    };
+    if let Some(index) = presets.step(selected.0, step) {
+        select_preset(presets, index, params, selected);
+    }
+}
+
+fn enter_gameplay_screen(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Gameplay);
+}
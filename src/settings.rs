@@ -0,0 +1,215 @@
+//! User settings, saved whenever the user changes them and restored on
+//! launch, except for whatever the launch chose itself. Natively they live in
+//! a file under the user's config directory; on the web they live in
+//! `localStorage`, under a key of each page's own.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MotionParameters, PlayMode, audio::AudioSettings, input::KeyBindings,
+    prediction::StopPointGizmo, screens::Screen, side_scroll, top_down,
+    top_down::overlay::DecompositionOverlay, trail::TrailDisplay,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Settings>();
+    app.init_resource::<LaunchChoices>();
+
+    // Load before `Startup`, so that the launch's own choices are in place to
+    // be kept and everything else is restored in time for the first frame.
+    app.add_systems(PreStartup, load_settings);
+    app.add_systems(Last, save_settings.run_if(not(in_state(Screen::Loading))));
+}
+
+/// A snapshot of everything that persists between launches, as last saved.
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Settings {
+    /// The last tuning used in side-scroll mode, or `None` to use the one the
+    /// app was launched with.
+    pub side_scroll_params: Option<MotionParameters>,
+    /// The last tuning used in top-down mode, or `None` to use the one the
+    /// app was launched with.
+    pub top_down_params: Option<MotionParameters>,
+    pub side_scroll_ground: side_scroll::Ground,
+    pub top_down_ground: top_down::Ground,
+    pub key_bindings: KeyBindings,
+    pub audio: AudioSettings,
+    pub trail: TrailDisplay,
+    pub velocity_vectors: bool,
+    pub stop_point: bool,
+}
+
+/// What the app was explicitly launched with, which saved settings never
+/// override.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LaunchChoices {
+    pub params: bool,
+    pub side_scroll_ground: bool,
+    pub top_down_ground: bool,
+}
+
+impl Settings {
+    /// The saved tuning of `mode`.
+    pub fn params(&self, mode: PlayMode) -> Option<MotionParameters> {
+        match mode {
+            PlayMode::SideScroll => self.side_scroll_params,
+            PlayMode::TopDown => self.top_down_params,
+        }
+    }
+
+    fn params_mut(&mut self, mode: PlayMode) -> &mut Option<MotionParameters> {
+        match mode {
+            PlayMode::SideScroll => &mut self.side_scroll_params,
+            PlayMode::TopDown => &mut self.top_down_params,
+        }
+    }
+
+    /// Insert each setting as the resource it controls, leaving alone those
+    /// the launch chose and starting with the tuning saved for `mode`.
+    pub fn apply(&self, world: &mut World, launch: LaunchChoices, mode: PlayMode) {
+        if !launch.params
+            && let Some(params) = self.params(mode)
+        {
+            world.insert_resource(params);
+        }
+        if !launch.side_scroll_ground {
+            world.insert_resource(self.side_scroll_ground);
+        }
+        if !launch.top_down_ground {
+            world.insert_resource(self.top_down_ground);
+        }
+        world.insert_resource(self.key_bindings.clone());
+        world.insert_resource(self.audio);
+        world.insert_resource(self.trail);
+        world.insert_resource(DecompositionOverlay(self.velocity_vectors));
+        world.insert_resource(StopPointGizmo(self.stop_point));
+    }
+}
+
+fn load_settings(world: &mut World) {
+    let Some(text) = storage::read() else {
+        return;
+    };
+    match ron::from_str::<Settings>(&text) {
+        Ok(settings) => {
+            let launch = *world.resource::<LaunchChoices>();
+            let mode = world
+                .get_resource::<State<PlayMode>>()
+                .map_or_else(PlayMode::default, |mode| *mode.get());
+            settings.apply(world, launch, mode);
+            world.insert_resource(settings);
+        }
+        Err(error) => warn!("Ignoring unreadable settings: {error}"),
+    }
+}
+
+/// Saves whatever the user has changed since the last save. Only settings
+/// that changed are written over, so the launch's own choices are never
+/// saved, and a tuning is only saved for the mode it was made in.
+fn save_settings(
+    mut started: Local<bool>,
+    mut settings: ResMut<Settings>,
+    mode: Res<State<PlayMode>>,
+    params: Res<MotionParameters>,
+    side_scroll_ground: Res<side_scroll::Ground>,
+    top_down_ground: Res<top_down::Ground>,
+    key_bindings: Res<KeyBindings>,
+    audio: Res<AudioSettings>,
+    trail: Res<TrailDisplay>,
+    velocity_vectors: Res<DecompositionOverlay>,
+    stop_point: Res<StopPointGizmo>,
+) {
+    // Everything up to the end of loading came from the launch, not the user.
+    if !std::mem::replace(&mut *started, true) {
+        return;
+    }
+
+    let mut current = settings.clone();
+    if params.is_changed() {
+        *current.params_mut(*mode.get()) = Some(*params);
+    }
+    if side_scroll_ground.is_changed() {
+        current.side_scroll_ground = *side_scroll_ground;
+    }
+    if top_down_ground.is_changed() {
+        current.top_down_ground = *top_down_ground;
+    }
+    if key_bindings.is_changed() {
+        current.key_bindings = key_bindings.clone();
+    }
+    if audio.is_changed() {
+        current.audio = *audio;
+    }
+    if trail.is_changed() {
+        current.trail = *trail;
+    }
+    if velocity_vectors.is_changed() {
+        current.velocity_vectors = velocity_vectors.0;
+    }
+    if stop_point.is_changed() {
+        current.stop_point = stop_point.0;
+    }
+    if *settings == current {
+        return;
+    }
+    *settings = current;
+
+    let result = ron::ser::to_string_pretty(&*settings, default())
+        .map_err(|error| error.to_string())
+        .and_then(|text| storage::write(&text));
+    if let Err(error) = result {
+        warn!("Failed to save settings: {error}");
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use std::{fs, path::PathBuf};
+
+    fn path() -> Option<PathBuf> {
+        Some(
+            dirs::config_dir()?
+                .join("char_motion_example")
+                .join("settings.ron"),
+        )
+    }
+
+    pub fn read() -> Option<String> {
+        fs::read_to_string(path()?).ok()
+    }
+
+    pub fn write(text: &str) -> Result<(), String> {
+        let path = path().ok_or("no config directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|error| error.to_string())?;
+        }
+        fs::write(path, text).map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    /// A key of the page's own, so that demo pages on the same site don't
+    /// share settings.
+    fn key() -> Option<String> {
+        let path = web_sys::window()?.location().pathname().ok()?;
+        Some(format!("char_motion_example.settings:{path}"))
+    }
+
+    fn local_storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    pub fn read() -> Option<String> {
+        local_storage()?.get_item(&key()?).ok()?
+    }
+
+    pub fn write(text: &str) -> Result<(), String> {
+        local_storage()
+            .ok_or("localStorage is unavailable")?
+            .set_item(&key().ok_or("the page has no path")?, text)
+            .map_err(|error| format!("{error:?}"))
+    }
+}
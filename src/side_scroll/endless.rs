@@ -0,0 +1,208 @@
+//! Ground that goes on forever, generated from a seed a chunk at a time ahead
+//! of the characters and forgotten once they've left it behind.
+//!
+//! Each chunk is worked out from the seed and its index alone, so the ground
+//! is the same however far anyone has travelled and whichever way they went.
+
+use std::{f32::consts::PI, ops::Range};
+
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AppSystems, PausableSystems, PlayMode,
+    side_scroll::{
+        Ground,
+        level::GroundProfile,
+        movement::{self, BasicMovementController},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<EndlessTerrain>();
+    app.init_resource::<EndlessTerrain>();
+    app.init_resource::<LoadedChunks>();
+
+    app.add_systems(
+        Update,
+        stream_chunks
+            .before(movement::apply_movement)
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(PlayMode::SideScroll)),
+    );
+}
+
+/// How wide each chunk of ground is, in pixels.
+pub const CHUNK_WIDTH: f32 = 500.0;
+
+/// How many points each chunk adds to the [`GroundProfile`].
+pub const POINTS_PER_CHUNK: usize = 25;
+
+/// How far ahead of and behind the characters the ground is kept generated.
+const DRAW_DISTANCE: f32 = 1000.0;
+
+/// The seed and difficulty of [`Ground::Endless`].
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[reflect(Resource)]
+pub struct EndlessTerrain {
+    pub seed: u64,
+    /// The steepest the ground ever gets, as rise over run.
+    pub steepness: f32,
+    /// How many bumps there are in every 1000 pixels, on average.
+    pub bump_frequency: f32,
+}
+
+impl Default for EndlessTerrain {
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            steepness: 0.5,
+            bump_frequency: 2.0,
+        }
+    }
+}
+
+/// Keeps the randomness of the edges and bumps of a chunk apart.
+const EDGE_STREAM: u64 = 0;
+const BUMP_STREAM: u64 = 1;
+
+impl EndlessTerrain {
+    /// The points of the chunks in `chunks`, from left to right.
+    pub fn points(&self, chunks: Range<i64>) -> Vec<Vec2> {
+        chunks.flat_map(|index| self.chunk(index)).collect()
+    }
+
+    /// The points of the `index`th chunk, from its left edge up to but not
+    /// including the next chunk's. Chunk 0 starts at `x = 0`.
+    ///
+    /// Half of the steepness goes to rising and falling between the edges of
+    /// chunks, and the other half to bumps along the way, so that together
+    /// they never go over it.
+    pub fn chunk(&self, index: i64) -> Vec<Vec2> {
+        let start = self.edge_height(index);
+        let end = self.edge_height(index + 1);
+
+        let mut rng = self.rng(index, BUMP_STREAM);
+        let expected = self.bump_frequency.max(0.0) * CHUNK_WIDTH / 1000.0;
+        let count = (expected + rng.r#gen::<f32>()).floor() as usize;
+        // Each bump has a slot of its own, so that they never overlap.
+        let bumps: Vec<_> = (0..count)
+            .map(|slot| {
+                let slot_width = CHUNK_WIDTH / count as f32;
+                let width = slot_width * rng.gen_range(0.5..=1.0);
+                let left = slot as f32 * slot_width + rng.gen_range(0.0..=slot_width - width);
+                let sign = if rng.r#gen() { 1.0 } else { -1.0 };
+                // The steepest part of a bump this high is half the steepness.
+                let height = sign * rng.gen_range(0.3..=1.0) * 0.5 * self.steepness * width / PI;
+                (left, width, height)
+            })
+            .collect();
+
+        (0..POINTS_PER_CHUNK)
+            .map(|point| {
+                let t = point as f32 / POINTS_PER_CHUNK as f32;
+                let x = t * CHUNK_WIDTH;
+                let smooth = t * t * (3.0 - 2.0 * t);
+                let bump_height: f32 = bumps
+                    .iter()
+                    .filter(|(left, width, _)| (*left..=left + width).contains(&x))
+                    .map(|(left, width, height)| {
+                        0.5 * height * (1.0 - (2.0 * PI * (x - left) / width).cos())
+                    })
+                    .sum();
+                Vec2::new(
+                    index as f32 * CHUNK_WIDTH + x,
+                    start + (end - start) * smooth + bump_height,
+                )
+            })
+            .collect()
+    }
+
+    /// The height of the ground where the `index`th chunk starts.
+    fn edge_height(&self, index: i64) -> f32 {
+        // Neighbouring edges are at most twice this apart, which the smoothed
+        // rise between them climbs at up to half the steepness.
+        let amplitude = self.steepness * CHUNK_WIDTH / 6.0;
+        amplitude * self.rng(index, EDGE_STREAM).gen_range(-1.0..=1.0)
+    }
+
+    fn rng(&self, index: i64, stream: u64) -> StdRng {
+        let mut seed = [0; 32];
+        seed[..8].copy_from_slice(&self.seed.to_le_bytes());
+        seed[8..16].copy_from_slice(&index.to_le_bytes());
+        seed[16..24].copy_from_slice(&stream.to_le_bytes());
+        StdRng::from_seed(seed)
+    }
+}
+
+/// The chunks currently in the [`GroundProfile`], if it's endless ground.
+#[derive(Resource, Debug, Default)]
+struct LoadedChunks(Option<Range<i64>>);
+
+/// The chunk that `x` is in.
+fn chunk_index(x: f32) -> i64 {
+    (x / CHUNK_WIDTH).floor() as i64
+}
+
+/// Generates the chunks that have come within [`DRAW_DISTANCE`] of any
+/// character and forgets those that have gone more than a chunk beyond it.
+fn stream_chunks(
+    ground: Res<Ground>,
+    terrain: Res<EndlessTerrain>,
+    mut profile: ResMut<GroundProfile>,
+    mut loaded: ResMut<LoadedChunks>,
+    characters: Query<&Transform, With<BasicMovementController>>,
+) {
+    if *ground != Ground::Endless {
+        loaded.0 = None;
+        return;
+    }
+    let Some((left, right)) = characters
+        .iter()
+        .map(|transform| transform.translation.x)
+        .fold(None, |range: Option<(f32, f32)>, x| {
+            Some(range.map_or((x, x), |(left, right)| (left.min(x), right.max(x))))
+        })
+    else {
+        return;
+    };
+    let needed = chunk_index(left - DRAW_DISTANCE)..chunk_index(right + DRAW_DISTANCE) + 1;
+
+    let chunks = match loaded.0.clone() {
+        Some(chunks)
+            if !ground.is_changed()
+                && !terrain.is_changed()
+                && chunks.start <= needed.end
+                && needed.start <= chunks.end =>
+        {
+            chunks
+        }
+        // Start afresh on switching to this ground, on a change of seed or
+        // difficulty, and when the characters have jumped clear of it.
+        _ => {
+            *profile = GroundProfile::new(terrain.points(needed.clone()));
+            loaded.0 = Some(needed);
+            return;
+        }
+    };
+
+    for index in (needed.start..chunks.start).rev() {
+        profile.prepend(&terrain.chunk(index));
+    }
+    for index in chunks.end..needed.end {
+        profile.extend(&terrain.chunk(index));
+    }
+    let start = chunks.start.min(needed.start);
+    let end = chunks.end.max(needed.end);
+
+    // Keep a chunk to spare either side, so that wandering back and forth
+    // over a chunk edge doesn't keep generating the same chunk.
+    let keep = needed.start - 1..needed.end + 1;
+    let forget_front = (keep.start - start).max(0);
+    let forget_back = (end - keep.end).max(0);
+    profile.trim_front(forget_front as usize * POINTS_PER_CHUNK);
+    profile.trim_back(forget_back as usize * POINTS_PER_CHUNK);
+    loaded.0 = Some(start + forget_front..end - forget_back);
+}
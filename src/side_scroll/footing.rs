@@ -0,0 +1,268 @@
+//! Jumping, and the walls and one-way platforms that side-scroll characters
+//! run into and land on.
+//!
+//! A character on the ground moves along it by its [`PositionAlongGround`].
+//! Once it jumps it moves freely through the world under gravity until it
+//! lands, either back on the ground or on top of a [`Platform`].
+
+use bevy::{color::palettes::tailwind, prelude::*};
+
+use crate::{
+    AppSystems, MotionParameters, PausableSystems, PlayMode,
+    player::MovementIntent,
+    side_scroll::{
+        Ground,
+        level::{GroundProfile, PositionAlongGround, move_along_ground},
+        movement::{self, BasicMovementController},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Footing>();
+    app.register_type::<Wall>();
+    app.register_type::<Platform>();
+
+    app.add_systems(
+        Update,
+        (
+            jump.before(movement::apply_movement),
+            (stop_at_walls, move_off_ground)
+                .chain()
+                .after(movement::apply_movement)
+                .before(move_along_ground),
+        )
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(PlayMode::SideScroll)),
+    );
+}
+
+/// Draws walls and platforms.
+pub(super) fn presentation_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        draw_walls_and_platforms
+            .after(move_along_ground)
+            .run_if(in_state(PlayMode::SideScroll)),
+    );
+}
+
+/// Upward speed given by a jump, in pixels per second.
+pub const JUMP_SPEED: f32 = 600.0;
+
+/// How far either side of its position a character meets a wall.
+pub const BODY_HALF_WIDTH: f32 = 12.0;
+
+/// What a side-scroll character is standing on.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Default)]
+#[reflect(Component)]
+pub enum Footing {
+    #[default]
+    Ground,
+    /// The top of a [`Platform`].
+    Platform(Entity),
+    /// Nothing, rising at `vertical_velocity` or falling while it's negative.
+    Airborne { vertical_velocity: f32 },
+}
+
+impl Footing {
+    pub fn is_airborne(&self) -> bool {
+        matches!(self, Footing::Airborne { .. })
+    }
+}
+
+/// A wall standing `height` tall on the ground at the entity's
+/// [`PositionAlongGround`], which characters can't walk or jump through.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct Wall {
+    pub height: f32,
+    /// The fraction of a character's speed it bounces back with, or zero to
+    /// stop it dead.
+    pub restitution: f32,
+}
+
+/// A one-way platform whose top runs `half_width` either side of the entity's
+/// [`Transform`]. Characters jump up through it and land on it from above.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct Platform {
+    pub half_width: f32,
+}
+
+/// Launches characters that want to jump and have something to jump off,
+/// keeping the speed they had along the ground.
+fn jump(
+    mut characters: Query<(
+        &mut Footing,
+        &mut BasicMovementController,
+        &MovementIntent,
+        &mut Transform,
+    )>,
+) {
+    for (mut footing, mut controller, intent, mut transform) in &mut characters {
+        if intent.0.y <= 0.0 || footing.is_airborne() {
+            continue;
+        }
+        let along_ground = transform.rotation * Vec3::X;
+        let velocity = controller.velocity();
+        controller.set_velocity(velocity * along_ground.x);
+        *footing = Footing::Airborne {
+            vertical_velocity: JUMP_SPEED + velocity * along_ground.y,
+        };
+        transform.rotation = Quat::IDENTITY;
+    }
+}
+
+/// Where a body moving from `from` to `to` stops against a wall at `wall`, if
+/// it gets that far.
+fn blocked_at(from: f32, to: f32, wall: f32) -> Option<f32> {
+    let left = wall - BODY_HALF_WIDTH;
+    let right = wall + BODY_HALF_WIDTH;
+    if from <= left && to > left {
+        Some(left)
+    } else if from >= right && to < right {
+        Some(right)
+    } else {
+        None
+    }
+}
+
+/// The nearest of `stops` to `from`.
+fn nearest_stop(from: f32, stops: impl Iterator<Item = (f32, Wall)>) -> Option<(f32, Wall)> {
+    stops.min_by(|a, b| (a.0 - from).abs().total_cmp(&(b.0 - from).abs()))
+}
+
+/// Stops characters walking along the ground at the first wall in their way.
+fn stop_at_walls(
+    time: Res<Time>,
+    mut characters: Query<
+        (
+            &mut BasicMovementController,
+            &mut PositionAlongGround,
+            &Footing,
+        ),
+        Without<Wall>,
+    >,
+    walls: Query<(&Wall, &PositionAlongGround)>,
+) {
+    let dt = time.delta_secs();
+    for (mut controller, mut arc_pos, footing) in &mut characters {
+        if *footing != Footing::Ground {
+            continue;
+        }
+        let to = arc_pos.0;
+        let from = to - controller.velocity() * dt;
+        let stops = walls
+            .iter()
+            .filter_map(|(wall, wall_pos)| Some((blocked_at(from, to, wall_pos.0)?, *wall)));
+        if let Some((stop, wall)) = nearest_stop(from, stops) {
+            arc_pos.0 = stop;
+            let velocity = controller.velocity();
+            controller.set_velocity(-wall.restitution * velocity);
+        }
+    }
+}
+
+/// Moves characters that are off the ground: along the platform they stand on,
+/// or through the air until they land on a platform or the ground.
+fn move_off_ground(
+    time: Res<Time>,
+    params: Res<MotionParameters>,
+    ground: Res<Ground>,
+    profile: Res<GroundProfile>,
+    mut characters: Query<
+        (
+            &mut BasicMovementController,
+            &mut Footing,
+            &mut PositionAlongGround,
+            &mut Transform,
+        ),
+        (Without<Wall>, Without<Platform>),
+    >,
+    walls: Query<(&Wall, &Transform)>,
+    platforms: Query<(Entity, &Platform, &Transform)>,
+) {
+    let dt = time.delta_secs();
+    let gravity = movement::gravity(&params);
+    for (mut controller, mut footing, mut arc_pos, mut transform) in &mut characters {
+        let from = transform.translation.xy();
+        let mut to = from + Vec2::X * controller.velocity() * dt;
+        match *footing {
+            Footing::Ground => continue,
+            Footing::Platform(platform) => {
+                let standing = platforms.get(platform).ok().filter(|(_, platform, top)| {
+                    (to.x - top.translation.x).abs() <= platform.half_width
+                });
+                *footing = match standing {
+                    Some(_) => Footing::Platform(platform),
+                    // Walked off the end.
+                    None => Footing::Airborne {
+                        vertical_velocity: 0.0,
+                    },
+                };
+            }
+            Footing::Airborne { vertical_velocity } => {
+                let vertical_velocity = vertical_velocity - gravity * dt;
+                to.y += vertical_velocity * dt;
+                *footing = Footing::Airborne { vertical_velocity };
+            }
+        }
+
+        let stops = walls.iter().filter_map(|(wall, base)| {
+            let base = base.translation.xy();
+            if to.y >= base.y + wall.height {
+                return None;
+            }
+            Some((blocked_at(from.x, to.x, base.x)?, *wall))
+        });
+        if let Some((stop, wall)) = nearest_stop(from.x, stops) {
+            to.x = stop;
+            let velocity = controller.velocity();
+            controller.set_velocity(-wall.restitution * velocity);
+        }
+
+        let falling =
+            matches!(*footing, Footing::Airborne { vertical_velocity } if vertical_velocity <= 0.0);
+        if falling {
+            // Only a platform passed through from above can be landed on.
+            let landing = platforms
+                .iter()
+                .filter(|(_, platform, top)| {
+                    let top = top.translation.xy();
+                    from.y >= top.y && to.y <= top.y && (to.x - top.x).abs() <= platform.half_width
+                })
+                .max_by(|a, b| a.2.translation.y.total_cmp(&b.2.translation.y));
+            if let Some((platform, _, top)) = landing {
+                to.y = top.translation.y;
+                *footing = Footing::Platform(platform);
+            }
+        }
+
+        arc_pos.0 = ground.arc_position_at(&profile, to.x);
+        if falling && footing.is_airborne() && to.y <= ground.placement(&profile, arc_pos.0).0.y {
+            // Back on the ground, where `move_along_ground` takes over.
+            *footing = Footing::Ground;
+            continue;
+        }
+        transform.translation = to.extend(transform.translation.z);
+    }
+}
+
+fn draw_walls_and_platforms(
+    walls: Query<(&Wall, &Transform)>,
+    platforms: Query<(&Platform, &Transform)>,
+    mut gizmos: Gizmos,
+) {
+    for (wall, base) in &walls {
+        let base = base.translation.xy();
+        gizmos.line_2d(base, base + wall.height * Vec2::Y, tailwind::STONE_300);
+    }
+    for (platform, top) in &platforms {
+        let top = top.translation.xy();
+        let half_width = platform.half_width * Vec2::X;
+        gizmos.line_2d(top - half_width, top + half_width, tailwind::BLUE_300);
+    }
+}
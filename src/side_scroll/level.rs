@@ -1,62 +1,131 @@
 use bevy::{color::palettes::tailwind, prelude::*};
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    PausableSystems, PlayMode,
+    input::{Action, KeyBindings},
+    level::LevelRoot,
     player::{Player, TrackingCameras},
-    side_scroll::movement,
+    side_scroll::{
+        footing::{Footing, Platform, Wall},
+        movement,
+    },
 };
 
-pub fn plugin(app: &mut App) {
+/// Maps each player's position along the ground into the world, and builds
+/// the walls and platforms of grounds that have them.
+pub(crate) fn plugin(app: &mut App) {
     app.register_type::<Ground>();
+    app.init_resource::<Ground>();
+    app.init_resource::<GroundProfile>();
     app.register_type::<PositionAlongGround>();
 
     app.add_systems(
         Update,
-        (swap_ground, move_along_ground, move_camera, draw_ground)
-            .chain()
-            .after(movement::apply_movement),
+        (
+            build_course.before(movement::apply_movement),
+            move_along_ground.after(movement::apply_movement),
+        )
+            .run_if(in_state(PlayMode::SideScroll)),
     );
 }
 
-#[derive(Resource, Reflect)]
+/// Switches, draws and follows the ground.
+pub(crate) fn presentation_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            swap_ground
+                .in_set(PausableSystems)
+                .before(move_along_ground),
+            (move_camera, draw_ground).chain().after(move_along_ground),
+        )
+            .run_if(in_state(PlayMode::SideScroll)),
+    );
+}
+
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(clap::ValueEnum))]
 pub enum Ground {
+    #[default]
     FlatPeriodic,
     Hills,
-    Loops,
+    /// Flat, with walls to run into and platforms to jump onto.
+    Platforms,
+    /// The [`GroundProfile`] built from the side-scroll level file.
+    Tiles,
+    /// The [`GroundProfile`] generated from the
+    /// [`EndlessTerrain`](super::endless::EndlessTerrain) seed as the players go.
+    Endless,
 }
 
-fn swap_ground(mut ground: ResMut<Ground>, input: Res<ButtonInput<KeyCode>>) {
-    if input.just_pressed(KeyCode::KeyC) {
-        *ground = match *ground {
+impl Ground {
+    /// The ground to switch to when cycling through grounds.
+    pub fn next(self) -> Self {
+        match self {
             Ground::FlatPeriodic => Ground::Hills,
-            Ground::Hills => Ground::FlatPeriodic,
-            Ground::Loops => Ground::Loops,
+            Ground::Hills => Ground::Platforms,
+            Ground::Platforms => Ground::Tiles,
+            Ground::Tiles => Ground::Endless,
+            Ground::Endless => Ground::FlatPeriodic,
         }
     }
-}
 
-#[derive(Component, Reflect)]
-pub struct PositionAlongGround(pub f32);
+    /// The ground before this one when cycling through grounds.
+    pub fn previous(self) -> Self {
+        match self {
+            Ground::FlatPeriodic => Ground::Endless,
+            Ground::Hills => Ground::FlatPeriodic,
+            Ground::Platforms => Ground::Hills,
+            Ground::Tiles => Ground::Platforms,
+            Ground::Endless => Ground::Tiles,
+        }
+    }
 
-pub fn move_along_ground(
-    mut objects: Query<(&mut Transform, &PositionAlongGround)>,
-    ground: Res<Ground>,
-) {
-    match *ground {
-        Ground::FlatPeriodic => {
-            for (mut tform, arc_pos) in &mut objects {
-                tform.translation = Vec3::new(arc_pos.0, 0., 0.);
+    /// The position along the ground below the point `x` across the world.
+    /// Grounds built from data are laid out by `profile`.
+    pub fn arc_position_at(self, profile: &GroundProfile, x: f32) -> f32 {
+        match self {
+            Ground::FlatPeriodic | Ground::Platforms => x,
+            Ground::Tiles | Ground::Endless => profile.arc_position_at(x),
+            Ground::Hills => {
+                // Every period of hills is the same length along the ground,
+                // however its hills are shaped, so `x` is within the stretch
+                // of ground of the period it's in.
+                let period = x.div_euclid(HILLS_PERIOD_LENGTH);
+                let period_arclength = hills_period_arclength();
+                let (mut low, mut high) =
+                    (period * period_arclength, (period + 1.0) * period_arclength);
+                for _ in 0..40 {
+                    let mid = 0.5 * (low + high);
+                    if self.placement(profile, mid).0.x < x {
+                        low = mid;
+                    } else {
+                        high = mid;
+                    }
+                }
+                0.5 * (low + high)
             }
         }
-        Ground::Hills => {
-            let period_length: f32 = 1000.0;
-            let max_height = 500.0;
-            let h_squared = max_height * max_height;
-            let period_arclength = (period_length * period_length + 4.0 * h_squared).sqrt();
-            let excess = 0.5 * (period_arclength - period_length);
-            let max_period = 4.0;
-            for (mut tform, arc_pos) in &mut objects {
-                let arc_pos_per_period = arc_pos.0 / period_arclength;
-                let period = arc_pos.0.div_euclid(period_arclength);
+    }
+
+    /// The point `arc_pos` along the ground, and the angle of the ground there.
+    /// Grounds built from data are laid out by `profile`.
+    pub fn placement(self, profile: &GroundProfile, arc_pos: f32) -> (Vec2, f32) {
+        match self {
+            Ground::FlatPeriodic | Ground::Platforms => (Vec2::new(arc_pos, 0.), 0.),
+            Ground::Tiles | Ground::Endless => profile.placement(arc_pos),
+            Ground::Hills => {
+                let period_length = HILLS_PERIOD_LENGTH;
+                let max_height = HILLS_MAX_HEIGHT;
+                let h_squared = max_height * max_height;
+                let period_arclength = (period_length * period_length + 4.0 * h_squared).sqrt();
+                let excess = 0.5 * (period_arclength - period_length);
+                let max_period = 4.0;
+
+                let arc_pos_per_period = arc_pos / period_arclength;
+                let period = arc_pos.div_euclid(period_arclength);
 
                 let period_folded =
                     ((period + max_period).rem_euclid(2.0 * max_period) - max_period).abs();
@@ -103,11 +172,232 @@ pub fn move_along_ground(
 
                 let x_pos = period * period_length + x_pos_in_period;
 
-                tform.translation = Vec3::new(x_pos, y_pos, 0.);
-                tform.rotation = Quat::from_rotation_z(angle);
+                (Vec2::new(x_pos, y_pos), angle)
             }
         }
-        Ground::Loops => todo!(),
+    }
+}
+
+/// How far across the world each period of [`Ground::Hills`] reaches.
+const HILLS_PERIOD_LENGTH: f32 = 1000.0;
+
+/// The height of the first period of [`Ground::Hills`]. Later periods are
+/// flatter on top and so not as high, keeping the same length along the
+/// ground.
+const HILLS_MAX_HEIGHT: f32 = 500.0;
+
+/// The length along the ground of each period of [`Ground::Hills`].
+fn hills_period_arclength() -> f32 {
+    (HILLS_PERIOD_LENGTH * HILLS_PERIOD_LENGTH + 4.0 * HILLS_MAX_HEIGHT * HILLS_MAX_HEIGHT).sqrt()
+}
+
+/// A ground of straight segments joining `points` from left to right, which
+/// carries on flat beyond either end.
+#[derive(Resource, Debug, Clone, Default, PartialEq)]
+pub struct GroundProfile {
+    points: Vec<Vec2>,
+    /// The position along the ground of each point, starting from the first
+    /// point's `x` so that positions match `x` up to the first slope.
+    arc_positions: Vec<f32>,
+}
+
+impl GroundProfile {
+    pub fn new(points: Vec<Vec2>) -> Self {
+        let mut arc_position = points.first().map_or(0.0, |first| first.x);
+        let arc_positions = points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                if index > 0 {
+                    arc_position += point.distance(points[index - 1]);
+                }
+                arc_position
+            })
+            .collect();
+        Self {
+            points,
+            arc_positions,
+        }
+    }
+
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    /// Carries the ground on to the right through `points`, keeping the
+    /// positions along it of the points it already has.
+    pub fn extend(&mut self, points: &[Vec2]) {
+        for &point in points {
+            let arc_position = match (self.points.last(), self.arc_positions.last()) {
+                (Some(last), Some(arc)) => arc + last.distance(point),
+                _ => point.x,
+            };
+            self.points.push(point);
+            self.arc_positions.push(arc_position);
+        }
+    }
+
+    /// Carries the ground on to the left through `points`, keeping the
+    /// positions along it of the points it already has.
+    pub fn prepend(&mut self, points: &[Vec2]) {
+        for &point in points.iter().rev() {
+            let arc_position = match (self.points.first(), self.arc_positions.first()) {
+                (Some(first), Some(arc)) => arc - first.distance(point),
+                _ => point.x,
+            };
+            self.points.insert(0, point);
+            self.arc_positions.insert(0, arc_position);
+        }
+    }
+
+    /// Forgets the first `count` points, without moving the rest.
+    pub fn trim_front(&mut self, count: usize) {
+        let count = count.min(self.points.len());
+        self.points.drain(..count);
+        self.arc_positions.drain(..count);
+    }
+
+    /// Forgets the last `count` points, without moving the rest.
+    pub fn trim_back(&mut self, count: usize) {
+        let keep = self.points.len().saturating_sub(count);
+        self.points.truncate(keep);
+        self.arc_positions.truncate(keep);
+    }
+
+    /// The point `arc_pos` along the ground, and the angle of the ground there.
+    pub fn placement(&self, arc_pos: f32) -> (Vec2, f32) {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return (Vec2::new(arc_pos, 0.0), 0.0);
+        };
+        let start = self.arc_positions[0];
+        let end = self.arc_positions[self.arc_positions.len() - 1];
+        if arc_pos <= start {
+            return (*first + (arc_pos - start) * Vec2::X, 0.0);
+        }
+        if arc_pos >= end {
+            return (*last + (arc_pos - end) * Vec2::X, 0.0);
+        }
+        let index = self.arc_positions.partition_point(|&arc| arc <= arc_pos);
+        let (from, to) = (self.points[index - 1], self.points[index]);
+        let (from_arc, to_arc) = (self.arc_positions[index - 1], self.arc_positions[index]);
+        let along = (arc_pos - from_arc) / (to_arc - from_arc);
+        let segment = to - from;
+        (from + along * segment, segment.y.atan2(segment.x))
+    }
+
+    /// The position along the ground below the point `x` across the world.
+    pub fn arc_position_at(&self, x: f32) -> f32 {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return x;
+        };
+        if x <= first.x {
+            return self.arc_positions[0] + x - first.x;
+        }
+        if x >= last.x {
+            return self.arc_positions[self.arc_positions.len() - 1] + x - last.x;
+        }
+        let index = self.points.partition_point(|point| point.x <= x);
+        let (from, to) = (self.points[index - 1], self.points[index]);
+        let along = (x - from.x) / (to.x - from.x);
+        self.arc_positions[index - 1] + along * from.distance(to)
+    }
+}
+
+impl std::fmt::Display for Ground {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ground::FlatPeriodic => write!(f, "Flat"),
+            Ground::Hills => write!(f, "Hills"),
+            Ground::Platforms => write!(f, "Platforms"),
+            Ground::Tiles => write!(f, "Tiles"),
+            Ground::Endless => write!(f, "Endless"),
+        }
+    }
+}
+
+fn swap_ground(
+    mut ground: ResMut<Ground>,
+    input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if bindings.just_pressed(Action::CycleGround, &input) {
+        *ground = ground.next();
+    }
+}
+
+/// Marks a wall or platform that belongs to the ground rather than the level.
+#[derive(Component, Debug)]
+struct Course;
+
+/// Swaps in the walls and platforms of the current ground whenever it or the
+/// level changes.
+fn build_course(
+    mut commands: Commands,
+    ground: Res<Ground>,
+    levels: Query<Entity, With<LevelRoot>>,
+    new_levels: Query<(), Added<LevelRoot>>,
+    course: Query<Entity, With<Course>>,
+) {
+    if !ground.is_changed() && new_levels.is_empty() {
+        return;
+    }
+    for piece in &course {
+        commands.entity(piece).despawn();
+    }
+    if *ground != Ground::Platforms {
+        return;
+    }
+    let wall = |position: f32, height: f32, restitution: f32| {
+        (
+            Name::new("Wall"),
+            Wall {
+                height,
+                restitution,
+            },
+            PositionAlongGround(position),
+        )
+    };
+    let platform = |x: f32, y: f32, half_width: f32| {
+        (
+            Name::new("Platform"),
+            Platform { half_width },
+            Transform::from_xyz(x, y, 0.0),
+        )
+    };
+    for level in &levels {
+        // A bouncy wall behind the start, too tall to jump.
+        commands.spawn((wall(-600.0, 400.0, 0.6), Course, ChildOf(level)));
+        // A low wall ahead, with steps up to a ledge above it.
+        commands.spawn((wall(700.0, 120.0, 0.0), Course, ChildOf(level)));
+        for (x, y, half_width) in [
+            (250.0, 120.0, 100.0),
+            (500.0, 240.0, 100.0),
+            (850.0, 150.0, 150.0),
+        ] {
+            commands.spawn((platform(x, y, half_width), Course, ChildOf(level)));
+        }
+        // The end of the course.
+        commands.spawn((wall(1400.0, 1000.0, 0.0), Course, ChildOf(level)));
+    }
+}
+
+#[derive(Component, Reflect)]
+pub struct PositionAlongGround(pub f32);
+
+pub fn move_along_ground(
+    mut objects: Query<(&mut Transform, &PositionAlongGround, Option<&Footing>)>,
+    ground: Res<Ground>,
+    profile: Res<GroundProfile>,
+) {
+    for (mut tform, arc_pos, footing) in &mut objects {
+        if footing.is_some_and(|footing| *footing != Footing::Ground) {
+            continue;
+        }
+        let (position, angle) = ground.placement(&profile, arc_pos.0);
+        tform.translation = position.extend(0.);
+        if *ground != Ground::FlatPeriodic {
+            tform.rotation = Quat::from_rotation_z(angle);
+        }
     }
 }
 
@@ -149,7 +439,7 @@ pub fn move_camera(
                 }
             }
         }
-        Ground::Hills => {
+        Ground::Hills | Ground::Platforms | Ground::Tiles | Ground::Endless => {
             for (player_transform, its_cameras) in &players {
                 for camera in its_cameras.iter() {
                     if let Ok((mut camera_transform, Projection::Orthographic(proj))) =
@@ -187,17 +477,17 @@ pub fn move_camera(
                 }
             }
         }
-        Ground::Loops => todo!(),
     }
 }
 
 pub fn draw_ground(
     players: Query<(&Transform, &PositionAlongGround), With<Player>>,
     ground: Res<Ground>,
+    profile: Res<GroundProfile>,
     mut gizmo: Gizmos,
 ) {
     match *ground {
-        Ground::FlatPeriodic => {
+        Ground::FlatPeriodic | Ground::Platforms => {
             for (tform, _) in &players {
                 let tf2 = tform.translation.xy();
                 let draw_distance = 500.0;
@@ -209,8 +499,8 @@ pub fn draw_ground(
             }
         }
         Ground::Hills => {
-            let period_length: f32 = 1000.0;
-            let max_height = 500.0;
+            let period_length = HILLS_PERIOD_LENGTH;
+            let max_height = HILLS_MAX_HEIGHT;
             let h_squared = max_height * max_height;
             let period_arclength = (period_length * period_length + 4.0 * h_squared).sqrt();
             let excess = 0.5 * (period_arclength - period_length);
@@ -249,6 +539,28 @@ pub fn draw_ground(
                 }
             }
         }
-        Ground::Loops => todo!(),
+        Ground::Tiles | Ground::Endless => {
+            let points = profile.points();
+            let (Some(first), Some(last)) = (points.first(), points.last()) else {
+                return;
+            };
+            // Carry the flat ends on to wherever the players have got to.
+            let draw_distance = 500.0;
+            let (left, right) =
+                players
+                    .iter()
+                    .fold((first.x, last.x), |(left, right), (tform, _)| {
+                        (
+                            left.min(tform.translation.x - draw_distance),
+                            right.max(tform.translation.x + draw_distance),
+                        )
+                    });
+            gizmo.linestrip_2d(
+                std::iter::once(Vec2::new(left, first.y))
+                    .chain(points.iter().copied())
+                    .chain(std::iter::once(Vec2::new(right, last.y))),
+                tailwind::BLUE_300,
+            );
+        }
     }
 }
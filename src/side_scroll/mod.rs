@@ -1,11 +1,31 @@
 use bevy::prelude::*;
 
+pub mod endless;
+pub mod footing;
 pub mod level;
 pub mod movement;
 pub mod player;
 
-pub use level::Ground;
+pub use endless::EndlessTerrain;
+pub use level::{Ground, GroundProfile};
 
-pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((movement::plugin, player::plugin, level::plugin));
+/// The side-scroll controller, its ground, walls and platforms, without any
+/// input or drawing.
+pub(crate) fn simulation_plugin(app: &mut App) {
+    app.add_plugins((
+        movement::plugin,
+        footing::plugin,
+        level::plugin,
+        endless::plugin,
+    ));
+}
+
+/// Keyboard control, camera and ground, wall and platform drawing for
+/// side-scroll mode.
+pub(crate) fn presentation_plugin(app: &mut App) {
+    app.add_plugins((
+        player::plugin,
+        footing::presentation_plugin,
+        level::presentation_plugin,
+    ));
 }
@@ -1,51 +1,115 @@
 use bevy::prelude::*;
 
 use crate::{
-    AppSystems, MotionParameters, player::MovementIntent, side_scroll::level::PositionAlongGround,
+    AppSystems, MotionParameters, PausableSystems, PlayMode,
+    player::{Controller, MotionRegime, MovementIntent, RegimeChanged, update_regime},
+    side_scroll::{footing::Footing, level::PositionAlongGround},
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<BasicMovementController>();
 
-    app.add_systems(Update, apply_movement.in_set(AppSystems::Update));
+    app.add_systems(
+        Update,
+        apply_movement
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(PlayMode::SideScroll)),
+    );
 }
 
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-#[require(MovementIntent)]
+#[require(MovementIntent, MotionRegime, Footing)]
 pub struct BasicMovementController {
     velocity: f32,
 }
 
-pub(super) fn apply_movement(
+impl BasicMovementController {
+    /// Signed speed along the ground, positive to the right.
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    pub fn set_velocity(&mut self, velocity: f32) {
+        self.velocity = velocity;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.velocity.abs()
+    }
+}
+
+impl Controller for BasicMovementController {
+    fn planar_velocity(&self) -> Vec2 {
+        Vec2::new(self.velocity, 0.0)
+    }
+}
+
+/// Advances a velocity along the ground by `dt` seconds, given the player's
+/// horizontal intent and the component of gravity along the ground. Returns
+/// the new velocity and the damping regime that produced it.
+pub fn step(
+    velocity: f32,
+    intent: f32,
+    gravity_along_ground: f32,
+    dt: f32,
+    params: &MotionParameters,
+) -> (f32, MotionRegime) {
+    let a_max = params.max_speed / params.t_acc * intent.signum();
+    let g_over_a = gravity_along_ground / a_max;
+    let slope_factor = (1.0 + g_over_a * g_over_a).sqrt() + g_over_a;
+
+    let target_velocity = slope_factor * params.max_speed * intent;
+    let scaled_timestep = dt / params.t_acc;
+    let (alpha, regime) = match target_velocity * velocity.signum() {
+        vel if vel < 0.0 => (params.alpha_rev, MotionRegime::Reversing),
+        0.0 => (params.alpha_stop, MotionRegime::Stopping),
+        vel if vel > 0.0 => (1.0, MotionRegime::Accelerating),
+        _ => (1.0, MotionRegime::Accelerating),
+    };
+    let velocity = (velocity + scaled_timestep * target_velocity) / (1.0 + alpha * scaled_timestep);
+    (velocity, regime)
+}
+
+/// The downward acceleration on side-scroll characters, in pixels per second
+/// squared.
+pub fn gravity(params: &MotionParameters) -> f32 {
+    50. * params.gravity_strength
+}
+
+pub fn apply_movement(
     time: Res<Time>,
     mut movement_query: Query<(
+        Entity,
         &mut BasicMovementController,
+        &mut MotionRegime,
         &MovementIntent,
         &mut PositionAlongGround,
         &Transform,
+        &Footing,
     )>,
     params: Res<MotionParameters>,
+    mut regime_events: EventWriter<RegimeChanged>,
 ) {
-    let gravity_global = 50. * params.gravity_strength * Vec3::NEG_Y;
-    for (mut controller, intent, mut arc_position, tf) in &mut movement_query {
+    let gravity_global = gravity(&params) * Vec3::NEG_Y;
+    for (entity, mut controller, regime, intent, mut arc_position, tf, footing) in
+        &mut movement_query
+    {
         let gravity_local = tf.rotation.inverse() * gravity_global;
+        let (velocity, new_regime) = step(
+            controller.velocity,
+            intent.0.x,
+            gravity_local.x,
+            time.delta_secs(),
+            &params,
+        );
+        update_regime(entity, regime, new_regime, &mut regime_events);
+        controller.velocity = velocity;
 
-        let a_max = params.max_speed / params.t_acc * intent.0.x.signum();
-        let g_over_a = gravity_local.x / a_max;
-        let slope_factor = (1.0 + g_over_a * g_over_a).sqrt() + g_over_a;
-
-        let target_velocity = slope_factor * params.max_speed * intent.0.x;
-        let scaled_timestep = time.delta_secs() / params.t_acc;
-        let alpha = match target_velocity * controller.velocity.signum() {
-            vel if vel < 0.0 => params.alpha_rev,
-            0.0 => params.alpha_stop,
-            vel if vel > 0.0 => 1.0,
-            _ => 1.0,
-        };
-        controller.velocity = (controller.velocity + scaled_timestep * target_velocity)
-            / (1.0 + alpha * scaled_timestep);
-
-        arc_position.0 += controller.velocity * time.delta_secs();
+        // Off the ground, `footing` moves the character instead.
+        if *footing == Footing::Ground {
+            arc_position.0 += controller.velocity * time.delta_secs();
+        }
     }
 }
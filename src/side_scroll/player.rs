@@ -1,66 +1,88 @@
-use bevy::{color::palettes::tailwind, prelude::*, render::camera::ScalingMode};
+use bevy::{prelude::*, render::camera::ScalingMode};
 
 use crate::{
-    AppSystems,
-    player::{self, MovementIntent, Player, TrackingCameras},
+    AppSystems, PausableSystems, PlayMode,
+    animation::{PlayerAssets, side_scroll_sprite},
+    input::{Action, KeyBindings},
+    motion_audio::Footsteps,
+    npc::{Behaviour, Npc},
+    player::{MovementIntent, Player},
     side_scroll::{level::PositionAlongGround, movement::BasicMovementController},
+    trail::MotionTrail,
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(player::plugin);
-
     app.add_systems(
         Update,
-        record_player_directional_input.in_set(AppSystems::RecordInput),
+        record_player_directional_input
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(PlayMode::SideScroll)),
     );
 }
 
-pub fn player(meshes: &mut Assets<Mesh>, mats: &mut Assets<ColorMaterial>) -> impl Bundle {
-    let mesh = Capsule2d::new(10.0, 30.0).mesh().build();
-
-    let player_mesh = meshes.add(mesh);
-    let player_color = mats.add(Color::from(tailwind::BLUE_400));
-
+pub fn player(player_assets: &PlayerAssets) -> impl Bundle {
     (
         Name::new("Player"),
         Player,
         BasicMovementController::default(),
+        Footsteps::default(),
         PositionAlongGround(0.0),
-        children![(
-            Transform::from_xyz(0.0, 25.0, 0.0),
-            Mesh2d(player_mesh),
-            MeshMaterial2d(player_color),
-        )],
+        // Footprints fall along the ground line rather than either side of it.
+        MotionTrail::default().with_footprint_offset(0.0),
+        children![side_scroll_sprite(player_assets)],
+        Visibility::default(),
+        Transform::default(),
+    )
+}
+
+/// A side-scroll character driven by `behaviour`, starting `position` along
+/// the ground.
+pub fn npc(player_assets: &PlayerAssets, position: f32, behaviour: Behaviour) -> impl Bundle {
+    (
+        Name::new("NPC"),
+        Npc,
+        behaviour,
+        BasicMovementController::default(),
+        PositionAlongGround(position),
+        children![side_scroll_sprite(player_assets)],
+        Visibility::default(),
         Transform::default(),
-        related!(
-            TrackingCameras[(
-                Name::new("Camera"),
-                Camera2d,
-                Projection::Orthographic(OrthographicProjection {
-                    scaling_mode: ScalingMode::FixedHorizontal {
-                        viewport_width: 1000.0,
-                    },
-                    ..OrthographicProjection::default_2d()
-                }),
-            )]
-        ),
     )
 }
 
-fn record_player_directional_input(
+/// A camera to follow a side-scroll player.
+pub fn camera() -> impl Bundle {
+    (
+        Name::new("Camera"),
+        Camera2d,
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedHorizontal {
+                viewport_width: 1000.0,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+    )
+}
+
+pub(crate) fn record_player_directional_input(
     input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     mut intent_query: Query<&mut MovementIntent, With<Player>>,
 ) {
     let mut intent = Vec2::ZERO;
 
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+    if bindings.pressed(Action::MoveLeft, &input) {
         intent.x -= 1.0;
     }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+    if bindings.pressed(Action::MoveRight, &input) {
         intent.x += 1.0;
     }
 
-    let intent = intent.normalize_or_zero();
+    let mut intent = intent.normalize_or_zero();
+    if bindings.pressed(Action::Jump, &input) {
+        intent.y = 1.0;
+    }
 
     for mut intent_instance in &mut intent_query {
         intent_instance.0 = intent;
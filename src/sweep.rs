@@ -0,0 +1,225 @@
+//! Measure how tunings feel by simulating a standard set of manoeuvres with
+//! each controller, so that tunings can be compared from data.
+//!
+//! The manoeuvres step the controllers directly at a fixed 60 Hz, without an
+//! app or a level, and every controller moves on flat ground.
+
+use std::{fmt::Write, str::FromStr};
+
+use bevy::prelude::*;
+
+use crate::{MotionParameters, side_scroll, top_down};
+
+/// The timestep each manoeuvre is simulated with.
+const DT: f32 = 1.0 / 60.0;
+
+/// How long a manoeuvre may run before its metric is left blank.
+const TIME_LIMIT: f32 = 60.0;
+
+/// The fraction of `max_speed` counted as having reached it.
+const ARRIVED: f32 = 0.9;
+
+/// The fraction of `max_speed` counted as having stopped.
+const STOPPED: f32 = 0.01;
+
+/// The values to try for one parameter. Parses from a single value, a comma
+/// separated list, or `start:end:count` for `count` evenly spaced values from
+/// `start` to `end` inclusive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepValues(pub Vec<f32>);
+
+impl FromStr for SweepValues {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |s: &str| {
+            s.trim()
+                .parse::<f32>()
+                .map_err(|error| format!("{s:?}: {error}"))
+        };
+        let parts: Vec<_> = s.split(':').collect();
+        match parts[..] {
+            [start, end, count] => {
+                let (start, end) = (number(start)?, number(end)?);
+                let count: usize = count
+                    .trim()
+                    .parse()
+                    .map_err(|error| format!("{count:?}: {error}"))?;
+                Ok(Self(match count {
+                    0 => Vec::new(),
+                    1 => vec![start],
+                    _ => (0..count)
+                        .map(|i| start.lerp(end, i as f32 / (count - 1) as f32))
+                        .collect(),
+                }))
+            }
+            [_] => s.split(',').map(number).collect::<Result<_, _>>().map(Self),
+            _ => Err(format!(
+                "expected a value, a list or start:end:count, got {s:?}"
+            )),
+        }
+    }
+}
+
+/// Which controller a row of metrics was measured with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepController {
+    SideScroll,
+    TopDown,
+}
+
+impl SweepController {
+    fn name(self) -> &'static str {
+        match self {
+            Self::SideScroll => "side_scroll",
+            Self::TopDown => "top_down",
+        }
+    }
+}
+
+/// The results of the standard manoeuvres for one tuning. A metric is `None`
+/// when its manoeuvre didn't finish within a minute, or doesn't apply to the
+/// controller.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    /// Seconds from rest to 90% of `max_speed`, holding a direction.
+    pub time_to_90: Option<f32>,
+    /// Distance covered from `max_speed` after letting go, until the speed
+    /// drops below 1% of `max_speed`.
+    pub stopping_distance: Option<f32>,
+    /// Seconds from `max_speed` to 90% of `max_speed` the opposite way.
+    pub reversal_time: Option<f32>,
+    /// How far a player at `max_speed` carries on in its original direction
+    /// after the intent turns 90°, until its sideways speed has died away.
+    pub turn_radius: Option<f32>,
+}
+
+/// Runs the manoeuvres with each controller for every combination of values.
+pub fn sweep(
+    max_speed: &SweepValues,
+    t_acc: &SweepValues,
+    alpha_stop: &SweepValues,
+    alpha_rev: &SweepValues,
+    alpha_turn: &SweepValues,
+) -> Vec<(SweepController, MotionParameters, Metrics)> {
+    let mut rows = Vec::new();
+    for &max_speed in &max_speed.0 {
+        for &t_acc in &t_acc.0 {
+            for &alpha_stop in &alpha_stop.0 {
+                for &alpha_rev in &alpha_rev.0 {
+                    for &alpha_turn in &alpha_turn.0 {
+                        let params = MotionParameters::full_with_separate_turn(
+                            max_speed, t_acc, alpha_stop, alpha_rev, alpha_turn,
+                        );
+                        rows.push((
+                            SweepController::SideScroll,
+                            params,
+                            side_scroll_metrics(&params),
+                        ));
+                        rows.push((SweepController::TopDown, params, top_down_metrics(&params)));
+                    }
+                }
+            }
+        }
+    }
+    rows
+}
+
+/// The rows of [`sweep`] as CSV, with a header row and blank cells for
+/// missing metrics.
+pub fn to_csv(rows: &[(SweepController, MotionParameters, Metrics)]) -> String {
+    let mut csv = String::from(
+        "controller,max_speed,t_acc,alpha_stop,alpha_rev,alpha_turn,\
+         time_to_90,stopping_distance,reversal_time,turn_radius\n",
+    );
+    let cell = |value: Option<f32>| value.map_or_else(String::new, |value| value.to_string());
+    for (controller, params, metrics) in rows {
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{},{},{}",
+            controller.name(),
+            params.max_speed,
+            params.t_acc,
+            params.alpha_stop,
+            params.alpha_rev,
+            params.alpha_turn,
+            cell(metrics.time_to_90),
+            cell(metrics.stopping_distance),
+            cell(metrics.reversal_time),
+            cell(metrics.turn_radius),
+        );
+    }
+    csv
+}
+
+/// Runs the manoeuvres with a side-scroll controller on flat ground. It only
+/// moves along the ground, so it has no turn radius.
+pub fn side_scroll_metrics(params: &MotionParameters) -> Metrics {
+    let step = |velocity: Vec2, intent: Vec2| {
+        Vec2::new(
+            side_scroll::movement::step(velocity.x, intent.x, 0.0, DT, params).0,
+            0.0,
+        )
+    };
+    Metrics {
+        // A side-scroll player can't turn 90°.
+        turn_radius: None,
+        ..measure(params, step)
+    }
+}
+
+/// Runs the manoeuvres with a top-down controller.
+pub fn top_down_metrics(params: &MotionParameters) -> Metrics {
+    measure(params, |velocity, intent| {
+        top_down::movement::step(velocity, intent, DT, params).0
+    })
+}
+
+fn measure(params: &MotionParameters, step: impl Fn(Vec2, Vec2) -> Vec2) -> Metrics {
+    let top_speed = params.max_speed * Vec2::X;
+    let arrived = ARRIVED * params.max_speed;
+    let stopped = STOPPED * params.max_speed;
+
+    let time_to_90 = simulate(Vec2::ZERO, Vec2::X, &step, |velocity| velocity.x >= arrived)
+        .map(|(time, _)| time);
+    let stopping_distance = simulate(top_speed, Vec2::ZERO, &step, |velocity| {
+        velocity.length() <= stopped
+    })
+    .map(|(_, displacement)| displacement.length());
+    let reversal_time = simulate(top_speed, Vec2::NEG_X, &step, |velocity| {
+        velocity.x <= -arrived
+    })
+    .map(|(time, _)| time);
+    let turn_radius = simulate(top_speed, Vec2::Y, &step, |velocity| {
+        velocity.x.abs() <= stopped
+    })
+    .map(|(_, displacement)| displacement.x);
+
+    Metrics {
+        time_to_90,
+        stopping_distance,
+        reversal_time,
+        turn_radius,
+    }
+}
+
+/// Steps from `velocity` with a constant `intent` until `done`, returning the
+/// time taken and the displacement on the way.
+fn simulate(
+    mut velocity: Vec2,
+    intent: Vec2,
+    step: impl Fn(Vec2, Vec2) -> Vec2,
+    done: impl Fn(Vec2) -> bool,
+) -> Option<(f32, Vec2)> {
+    let mut time = 0.0;
+    let mut displacement = Vec2::ZERO;
+    while !done(velocity) {
+        if time > TIME_LIMIT {
+            return None;
+        }
+        velocity = step(velocity, intent);
+        displacement += velocity * DT;
+        time += DT;
+    }
+    Some((time, displacement))
+}
@@ -49,6 +49,17 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// A text label for the left-hand column of a two-column settings grid.
+pub fn grid_label(text: impl Into<String>) -> impl Bundle {
+    (
+        label(text),
+        Node {
+            justify_self: JustifySelf::End,
+            ..default()
+        },
+    )
+}
+
 /// A large rounded button with text and an action defined as an [`Observer`].
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -92,6 +103,42 @@ where
     )
 }
 
+/// A value shown between two small buttons that step it back and forth, each with an action defined as an [`Observer`].
+pub fn stepper<E1, B1, M1, I1, E2, B2, M2, I2>(
+    value: impl Bundle,
+    previous: I1,
+    next: I2,
+) -> impl Bundle
+where
+    E1: Event,
+    B1: Bundle,
+    I1: IntoObserverSystem<E1, B1, M1>,
+    E2: Event,
+    B2: Bundle,
+    I2: IntoObserverSystem<E2, B2, M2>,
+{
+    (
+        Name::new("Stepper"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            button_small("<", previous),
+            (
+                Name::new("Current Value"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![value],
+            ),
+            button_small(">", next),
+        ],
+    )
+}
+
 /// A simple button with text and an action defined as an [`Observer`]. The button's layout is provided by `button_bundle`.
 fn button_base<E, B, M, I>(
     text: impl Into<String>,
@@ -0,0 +1,547 @@
+//! Levels laid out as grids of characters in `.level.ron` files, loaded
+//! before gameplay starts and built into the `Tiles` ground of either mode.
+//!
+//! Each character is one tile:
+//!
+//! | Tile           | Side scroll                                 | Top down           |
+//! |----------------|---------------------------------------------|--------------------|
+//! | `.` or space   | empty                                       | empty              |
+//! | `#`            | ground, whose top in each column is walked  | a wall block       |
+//! | `=`            | a one-way platform                          |                    |
+//! | `\|`           | a wall                                      |                    |
+//! | `o`            |                                             | a round obstacle   |
+//! | `~`            |                                             | ice                |
+//! | `P`            | where players spawn                         | where players spawn|
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PlayMode,
+    asset_tracking::LoadResource,
+    level::LevelRoot,
+    player::Player,
+    side_scroll::{
+        self,
+        footing::{Footing, Platform, Wall},
+        level::{GroundProfile, PositionAlongGround},
+        movement::BasicMovementController,
+    },
+    top_down::{self, collision::Obstacle, movement::MovementController, surface::Surface},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<Tilemap>();
+    app.init_asset_loader::<TilemapLoader>();
+    app.register_type::<LevelAssets>();
+    app.load_resource::<LevelAssets>();
+
+    app.add_systems(
+        Update,
+        (
+            build_side_scroll_level
+                .before(side_scroll::movement::apply_movement)
+                .run_if(in_state(PlayMode::SideScroll)),
+            build_top_down_level
+                .before(top_down::movement::apply_movement)
+                .run_if(in_state(PlayMode::TopDown)),
+        )
+            .run_if(resource_exists::<LevelAssets>),
+    );
+}
+
+/// One cell of a [`Tilemap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Tile {
+    #[default]
+    Empty,
+    Solid,
+    Platform,
+    Wall,
+    Round,
+    Ice,
+    Spawn,
+}
+
+impl Tile {
+    pub fn from_char(tile: char) -> Option<Self> {
+        match tile {
+            '.' | ' ' => Some(Tile::Empty),
+            '#' => Some(Tile::Solid),
+            '=' => Some(Tile::Platform),
+            '|' => Some(Tile::Wall),
+            'o' => Some(Tile::Round),
+            '~' => Some(Tile::Ice),
+            'P' => Some(Tile::Spawn),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Solid => '#',
+            Tile::Platform => '=',
+            Tile::Wall => '|',
+            Tile::Round => 'o',
+            Tile::Ice => '~',
+            Tile::Spawn => 'P',
+        }
+    }
+}
+
+/// A character in a level file that isn't a [`Tile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownTile {
+    pub row: usize,
+    pub column: usize,
+    pub tile: char,
+}
+
+impl std::fmt::Display for UnknownTile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown tile {:?} at row {}, column {}",
+            self.tile, self.row, self.column
+        )
+    }
+}
+
+impl std::error::Error for UnknownTile {}
+
+/// How much of its damping a controller keeps on ice.
+const ICE_GRIP: f32 = 0.2;
+
+/// A level as it's written in a `.level.ron` file. The bottom left corner of
+/// the grid is at the origin of the world.
+#[derive(Asset, TypePath, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Tilemap {
+    /// The width and height of each tile, in pixels.
+    pub tile_size: f32,
+    /// The rows of tiles from the top down, one character per tile. Short
+    /// rows are padded with empty tiles.
+    pub rows: Vec<String>,
+}
+
+impl Tilemap {
+    /// Checks that every character is a [`Tile`].
+    pub fn validate(&self) -> Result<(), UnknownTile> {
+        for (row, tiles) in self.rows.iter().enumerate() {
+            for (column, tile) in tiles.chars().enumerate() {
+                if Tile::from_char(tile).is_none() {
+                    return Err(UnknownTile { row, column, tile });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn tile(&self, column: usize, row: usize) -> Tile {
+        self.rows
+            .get(row)
+            .and_then(|tiles| tiles.chars().nth(column))
+            .and_then(Tile::from_char)
+            .unwrap_or_default()
+    }
+
+    /// Replaces a tile, padding its row with empty tiles if it's too short.
+    pub fn set_tile(&mut self, column: usize, row: usize, tile: Tile) {
+        let Some(tiles) = self.rows.get_mut(row) else {
+            return;
+        };
+        let mut chars: Vec<_> = tiles.chars().collect();
+        if chars.len() <= column {
+            chars.resize(column + 1, Tile::Empty.to_char());
+        }
+        chars[column] = tile.to_char();
+        *tiles = chars.into_iter().collect();
+    }
+
+    /// Raises or lowers the side-scroll ground in `column` so that its top is
+    /// the tile in `row`, leaving anything but ground and empty tiles alone.
+    pub fn set_surface(&mut self, column: usize, row: usize) {
+        for other_row in 0..self.height() {
+            match (other_row >= row, self.tile(column, other_row)) {
+                (true, Tile::Empty) => self.set_tile(column, other_row, Tile::Solid),
+                (false, Tile::Solid) => self.set_tile(column, other_row, Tile::Empty),
+                _ => {}
+            }
+        }
+    }
+
+    /// The column and row of the tile under a point in the world, if the
+    /// point is on the map.
+    pub fn cell_at(&self, position: Vec2) -> Option<(usize, usize)> {
+        let cell = (position / self.tile_size).floor();
+        if cell.x < 0.0 || cell.y < 0.0 {
+            return None;
+        }
+        let (column, from_bottom) = (cell.x as usize, cell.y as usize);
+        (column < self.width() && from_bottom < self.height())
+            .then(|| (column, self.height() - 1 - from_bottom))
+    }
+
+    /// The centre of a tile in the world.
+    pub fn tile_centre(&self, column: usize, row: usize) -> Vec2 {
+        Vec2::new(column as f32 + 0.5, (self.height() - row) as f32 - 0.5) * self.tile_size
+    }
+
+    /// The height of the top edge of the tiles in `row`.
+    fn top_of_row(&self, row: usize) -> f32 {
+        (self.height() - row) as f32 * self.tile_size
+    }
+
+    /// Each horizontal run of `tile` as its row, first column and length.
+    fn runs(&self, tile: Tile) -> Vec<(usize, usize, usize)> {
+        let mut runs = Vec::new();
+        for row in 0..self.height() {
+            let mut start = None;
+            for column in 0..=self.width() {
+                match (self.tile(column, row) == tile, start) {
+                    (true, None) => start = Some(column),
+                    (false, Some(first)) => {
+                        runs.push((row, first, column - first));
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        runs
+    }
+
+    /// The centre and size of each horizontal run of `tile`.
+    fn run_rectangles(&self, tile: Tile) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.runs(tile).into_iter().map(|(row, first, length)| {
+            let left = self.tile_centre(first, row);
+            let right = self.tile_centre(first + length - 1, row);
+            (
+                (left + right) / 2.0,
+                Vec2::new(length as f32, 1.0) * self.tile_size,
+            )
+        })
+    }
+
+    /// Where players spawn, in the order they appear reading down the map.
+    pub fn spawn_points(&self) -> Vec<Vec2> {
+        (0..self.height())
+            .flat_map(|row| (0..self.width()).map(move |column| (column, row)))
+            .filter(|&(column, row)| self.tile(column, row) == Tile::Spawn)
+            .map(|(column, row)| self.tile_centre(column, row))
+            .collect()
+    }
+
+    /// The side-scroll ground, through the middle of the top of the highest
+    /// solid tile in each column. Columns without one carry on the height
+    /// of the column before.
+    pub fn ground_profile(&self) -> GroundProfile {
+        let surface: Vec<_> = (0..self.width())
+            .map(|column| {
+                (0..self.height())
+                    .find(|&row| self.tile(column, row) == Tile::Solid)
+                    .map(|row| self.top_of_row(row))
+            })
+            .collect();
+        let Some(first_height) = surface.iter().flatten().next() else {
+            return GroundProfile::default();
+        };
+        let mut height = *first_height;
+        let points = surface
+            .iter()
+            .enumerate()
+            .map(|(column, top)| {
+                height = top.unwrap_or(height);
+                Vec2::new((column as f32 + 0.5) * self.tile_size, height)
+            })
+            .collect();
+        GroundProfile::new(points)
+    }
+
+    /// The side-scroll platforms, one for each horizontal run of platform
+    /// tiles, level with the top of the run.
+    pub fn platforms(&self) -> Vec<(Vec2, Platform)> {
+        self.run_rectangles(Tile::Platform)
+            .map(|(centre, size)| {
+                (
+                    centre + size.y / 2.0 * Vec2::Y,
+                    Platform {
+                        half_width: size.x / 2.0,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// The side-scroll walls, one for each vertical run of wall tiles, as a
+    /// position along `profile` and a wall reaching up to the top of the run.
+    pub fn walls(&self, profile: &GroundProfile) -> Vec<(f32, Wall)> {
+        let mut walls = Vec::new();
+        for column in 0..self.width() {
+            let mut rows = 0..self.height();
+            while let Some(top) = rows.find(|&row| self.tile(column, row) == Tile::Wall) {
+                rows.find(|&row| self.tile(column, row) != Tile::Wall);
+                let arc_pos = profile.arc_position_at(self.tile_centre(column, top).x);
+                let (base, _) = profile.placement(arc_pos);
+                let height = self.top_of_row(top) - base.y;
+                if height > 0.0 {
+                    walls.push((
+                        arc_pos,
+                        Wall {
+                            height,
+                            restitution: 0.0,
+                        },
+                    ));
+                }
+            }
+        }
+        walls
+    }
+
+    /// The top-down obstacles: a box for each horizontal run of solid tiles
+    /// and a circle for each round one.
+    pub fn obstacles(&self) -> Vec<(Vec2, Obstacle)> {
+        let boxes = self
+            .run_rectangles(Tile::Solid)
+            .map(|(centre, size)| (centre, Obstacle::Aabb(Rectangle::from_size(size))));
+        let circles = (0..self.height())
+            .flat_map(|row| (0..self.width()).map(move |column| (column, row)))
+            .filter(|&(column, row)| self.tile(column, row) == Tile::Round)
+            .map(|(column, row)| {
+                (
+                    self.tile_centre(column, row),
+                    Obstacle::Circle(Circle::new(self.tile_size / 2.0)),
+                )
+            });
+        boxes.chain(circles).collect()
+    }
+
+    /// The top-down surfaces, one for each horizontal run of ice tiles.
+    pub fn surfaces(&self) -> Vec<(Vec2, Surface)> {
+        self.run_rectangles(Tile::Ice)
+            .map(|(centre, size)| {
+                (
+                    centre,
+                    Surface {
+                        half_size: size / 2.0,
+                        grip: ICE_GRIP,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Default, TypePath)]
+struct TilemapLoader;
+
+impl AssetLoader for TilemapLoader {
+    type Asset = Tilemap;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Tilemap, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let tilemap: Tilemap = ron::de::from_bytes(&bytes)?;
+        tilemap.validate()?;
+        Ok(tilemap)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+/// The level file of each play mode.
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct LevelAssets {
+    #[dependency]
+    pub side_scroll: Handle<Tilemap>,
+    #[dependency]
+    pub top_down: Handle<Tilemap>,
+}
+
+impl LevelAssets {
+    pub const SIDE_SCROLL_PATH: &str = "levels/side_scroll.level.ron";
+    pub const TOP_DOWN_PATH: &str = "levels/top_down.level.ron";
+}
+
+impl FromWorld for LevelAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            side_scroll: assets.load(Self::SIDE_SCROLL_PATH),
+            top_down: assets.load(Self::TOP_DOWN_PATH),
+        }
+    }
+}
+
+/// Marks an entity built from a level file, to be despawned when the ground
+/// changes.
+#[derive(Component, Debug)]
+struct FromTiles;
+
+/// Whether the ground or level changed, so that the level file needs
+/// building and the players moving to its spawn points, and whether the file
+/// was edited, so that it only needs building again.
+fn changes(
+    ground_changed: bool,
+    new_levels: &Query<(), Added<LevelRoot>>,
+    events: &mut EventReader<AssetEvent<Tilemap>>,
+    handle: &Handle<Tilemap>,
+) -> (bool, bool) {
+    let edited = events
+        .read()
+        .any(|event| event.is_modified(handle) || event.is_loaded_with_dependencies(handle));
+    (ground_changed || !new_levels.is_empty(), edited)
+}
+
+/// Builds the side-scroll level file into the ground, walls and platforms of
+/// [`side_scroll::Ground::Tiles`], and moves the players to its spawn points
+/// unless the file was only edited.
+fn build_side_scroll_level(
+    mut commands: Commands,
+    ground: Res<side_scroll::Ground>,
+    mut profile: ResMut<GroundProfile>,
+    level_assets: Res<LevelAssets>,
+    tilemaps: Res<Assets<Tilemap>>,
+    mut events: EventReader<AssetEvent<Tilemap>>,
+    levels: Query<Entity, With<LevelRoot>>,
+    new_levels: Query<(), Added<LevelRoot>>,
+    built: Query<Entity, With<FromTiles>>,
+    mut players: Query<
+        (
+            &mut PositionAlongGround,
+            &mut BasicMovementController,
+            &mut Footing,
+        ),
+        With<Player>,
+    >,
+) {
+    let handle = &level_assets.side_scroll;
+    let (respawn, edited) = changes(ground.is_changed(), &new_levels, &mut events, handle);
+    if !respawn && !edited {
+        return;
+    }
+    for entity in &built {
+        commands.entity(entity).despawn();
+    }
+    if *ground != side_scroll::Ground::Tiles {
+        return;
+    }
+    let Some(tilemap) = tilemaps.get(handle) else {
+        return;
+    };
+
+    *profile = tilemap.ground_profile();
+    for level in &levels {
+        for (arc_pos, wall) in tilemap.walls(&profile) {
+            commands.spawn((
+                Name::new("Wall"),
+                FromTiles,
+                wall,
+                PositionAlongGround(arc_pos),
+                ChildOf(level),
+            ));
+        }
+        for (top, platform) in tilemap.platforms() {
+            commands.spawn((
+                Name::new("Platform"),
+                FromTiles,
+                platform,
+                Transform::from_translation(top.extend(0.0)),
+                ChildOf(level),
+            ));
+        }
+    }
+    if !respawn {
+        return;
+    }
+    for ((mut arc_pos, mut controller, mut footing), spawn) in
+        players.iter_mut().zip(tilemap.spawn_points())
+    {
+        arc_pos.0 = profile.arc_position_at(spawn.x);
+        controller.set_velocity(0.0);
+        *footing = Footing::Ground;
+    }
+}
+
+/// Builds the top-down level file into the obstacles and surfaces of
+/// [`top_down::Ground::Tiles`], and moves the players to its spawn points
+/// unless the file was only edited.
+fn build_top_down_level(
+    mut commands: Commands,
+    ground: Res<top_down::Ground>,
+    level_assets: Res<LevelAssets>,
+    tilemaps: Res<Assets<Tilemap>>,
+    mut events: EventReader<AssetEvent<Tilemap>>,
+    levels: Query<Entity, With<LevelRoot>>,
+    new_levels: Query<(), Added<LevelRoot>>,
+    built: Query<Entity, With<FromTiles>>,
+    mut players: Query<(&mut Transform, &mut MovementController), With<Player>>,
+) {
+    let handle = &level_assets.top_down;
+    let (respawn, edited) = changes(ground.is_changed(), &new_levels, &mut events, handle);
+    if !respawn && !edited {
+        return;
+    }
+    for entity in &built {
+        commands.entity(entity).despawn();
+    }
+    if *ground != top_down::Ground::Tiles {
+        return;
+    }
+    let Some(tilemap) = tilemaps.get(handle) else {
+        return;
+    };
+
+    for level in &levels {
+        for (centre, obstacle) in tilemap.obstacles() {
+            commands.spawn((
+                Name::new("Obstacle"),
+                FromTiles,
+                obstacle,
+                Transform::from_translation(centre.extend(0.0)),
+                ChildOf(level),
+            ));
+        }
+        for (centre, surface) in tilemap.surfaces() {
+            commands.spawn((
+                Name::new("Ice"),
+                FromTiles,
+                surface,
+                Transform::from_translation(centre.extend(0.0)),
+                ChildOf(level),
+            ));
+        }
+    }
+    if !respawn {
+        return;
+    }
+    for ((mut transform, mut controller), spawn) in players.iter_mut().zip(tilemap.spawn_points()) {
+        transform.translation = spawn.extend(transform.translation.z);
+        controller.set_velocity(Vec2::ZERO);
+    }
+}
@@ -0,0 +1,251 @@
+//! Static obstacles that top-down controllers with a [`CircleCollider`] slide
+//! along instead of passing through.
+//!
+//! Each move is swept against every obstacle grown by the collider's radius,
+//! so that no speed or timestep can tunnel through a thin wall.
+
+use bevy::{color::palettes::tailwind, prelude::*};
+
+use crate::PlayMode;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Obstacle>();
+    app.register_type::<CircleCollider>();
+}
+
+/// Outlines every obstacle.
+pub(super) fn presentation_plugin(app: &mut App) {
+    app.add_systems(Update, draw_obstacles.run_if(in_state(PlayMode::TopDown)));
+}
+
+/// A shape that top-down controllers can't move through, placed by the
+/// entity's [`Transform`] in the same space as the controllers.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+#[require(Transform)]
+pub enum Obstacle {
+    /// An axis-aligned box, which ignores the entity's rotation.
+    Aabb(Rectangle),
+    Circle(Circle),
+    /// A convex polygon with vertices relative to the entity, in either
+    /// winding order.
+    ConvexPolygon(Vec<Vec2>),
+}
+
+/// The footprint of a top-down controller that collides with [`Obstacle`]s.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+pub struct CircleCollider(pub Circle);
+
+/// How many times a move may hit something and carry on along it.
+const MAX_SLIDES: usize = 4;
+
+/// How far a collider is kept from the surfaces it touches, so that the next
+/// sweep starts outside them.
+const SKIN: f32 = 0.01;
+
+/// An obstacle placed in the world.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlacedObstacle {
+    Circle {
+        centre: Vec2,
+        radius: f32,
+    },
+    /// Vertices in anticlockwise order.
+    Polygon(Vec<Vec2>),
+}
+
+impl Obstacle {
+    pub fn place(&self, transform: &Transform) -> PlacedObstacle {
+        let centre = transform.translation.xy();
+        match self {
+            Obstacle::Aabb(rectangle) => {
+                let Vec2 { x, y } = rectangle.half_size;
+                PlacedObstacle::Polygon(vec![
+                    centre + Vec2::new(-x, -y),
+                    centre + Vec2::new(x, -y),
+                    centre + Vec2::new(x, y),
+                    centre + Vec2::new(-x, y),
+                ])
+            }
+            Obstacle::Circle(circle) => PlacedObstacle::Circle {
+                centre,
+                radius: circle.radius,
+            },
+            Obstacle::ConvexPolygon(vertices) => {
+                let mut vertices: Vec<_> = vertices
+                    .iter()
+                    .map(|&vertex| transform.transform_point(vertex.extend(0.0)).xy())
+                    .collect();
+                if signed_area(&vertices) < 0.0 {
+                    vertices.reverse();
+                }
+                PlacedObstacle::Polygon(vertices)
+            }
+        }
+    }
+}
+
+fn signed_area(vertices: &[Vec2]) -> f32 {
+    edges(vertices).map(|(a, b)| a.perp_dot(b)).sum::<f32>() / 2.0
+}
+
+fn edges(vertices: &[Vec2]) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// The outward normal of an anticlockwise edge.
+fn outward_normal(a: Vec2, b: Vec2) -> Vec2 {
+    let edge = b - a;
+    Vec2::new(edge.y, -edge.x).normalize_or_zero()
+}
+
+impl PlacedObstacle {
+    /// The fraction of `delta` a circle of `radius` can move from `start`
+    /// before touching this obstacle, and the obstacle's normal there.
+    fn sweep(&self, start: Vec2, delta: Vec2, radius: f32) -> Option<(f32, Vec2)> {
+        match self {
+            PlacedObstacle::Circle {
+                centre,
+                radius: obstacle_radius,
+            } => sweep_circle(start, delta, *centre, obstacle_radius + radius),
+            PlacedObstacle::Polygon(vertices) => {
+                let faces = edges(vertices).filter_map(|(a, b)| {
+                    let normal = outward_normal(a, b);
+                    let from = (start - a).dot(normal) - radius;
+                    let to = (start + delta - a).dot(normal) - radius;
+                    if from < -SKIN || to >= 0.0 || from <= to {
+                        return None;
+                    }
+                    let t = (from / (from - to)).max(0.0);
+                    let along = (start + t * delta - a).dot((b - a).normalize_or_zero());
+                    (0.0..=a.distance(b))
+                        .contains(&along)
+                        .then_some((t, normal))
+                });
+                let corners = vertices
+                    .iter()
+                    .filter_map(|&vertex| sweep_circle(start, delta, vertex, radius));
+                faces.chain(corners).min_by(|a, b| a.0.total_cmp(&b.0))
+            }
+        }
+    }
+
+    /// How far, and in which direction, to push a circle of `radius` at
+    /// `centre` so that it no longer overlaps this obstacle.
+    fn penetration(&self, centre: Vec2, radius: f32) -> Option<Vec2> {
+        match self {
+            PlacedObstacle::Circle {
+                centre: obstacle_centre,
+                radius: obstacle_radius,
+            } => {
+                let offset = centre - obstacle_centre;
+                let depth = obstacle_radius + radius - offset.length();
+                (depth > 0.0).then(|| offset.normalize_or(Vec2::X) * depth)
+            }
+            PlacedObstacle::Polygon(vertices) => {
+                let (deepest_distance, deepest_normal) = edges(vertices)
+                    .map(|(a, b)| {
+                        let normal = outward_normal(a, b);
+                        ((centre - a).dot(normal), normal)
+                    })
+                    .max_by(|a, b| a.0.total_cmp(&b.0))?;
+                if deepest_distance <= 0.0 {
+                    // The centre is inside, so leave by the nearest face.
+                    return Some(deepest_normal * (radius - deepest_distance));
+                }
+                let closest = edges(vertices)
+                    .map(|(a, b)| closest_on_segment(centre, a, b))
+                    .min_by(|a, b| centre.distance(*a).total_cmp(&centre.distance(*b)))?;
+                let offset = centre - closest;
+                let depth = radius - offset.length();
+                (depth > 0.0).then(|| offset.normalize_or(deepest_normal) * depth)
+            }
+        }
+    }
+}
+
+fn sweep_circle(start: Vec2, delta: Vec2, centre: Vec2, radius: f32) -> Option<(f32, Vec2)> {
+    let offset = start - centre;
+    let a = delta.length_squared();
+    let b = offset.dot(delta);
+    let c = offset.length_squared() - radius * radius;
+    // Moving away can't hit the circle, and overlaps are left to `penetration`.
+    if a == 0.0 || b >= 0.0 || c < 0.0 {
+        return None;
+    }
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let t = (-b - discriminant.sqrt()) / a;
+    (t <= 1.0).then(|| {
+        let t = t.max(0.0);
+        (t, (start + t * delta - centre) / radius)
+    })
+}
+
+fn closest_on_segment(point: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    let segment = b - a;
+    let along = if segment.length_squared() > 0.0 {
+        ((point - a).dot(segment) / segment.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    a + along * segment
+}
+
+/// Moves a circle of `radius` at `position` by `velocity` for `dt` seconds,
+/// sliding along any obstacles in the way. Returns the new position and the
+/// velocity left once the parts into each obstacle touched are removed.
+pub fn move_and_slide(
+    mut position: Vec2,
+    mut velocity: Vec2,
+    dt: f32,
+    radius: f32,
+    obstacles: &[PlacedObstacle],
+) -> (Vec2, Vec2) {
+    for obstacle in obstacles {
+        if let Some(push) = obstacle.penetration(position, radius) {
+            position += push;
+            let normal = push.normalize_or_zero();
+            velocity -= normal * velocity.dot(normal).min(0.0);
+        }
+    }
+
+    let mut remaining = velocity * dt;
+    for _ in 0..MAX_SLIDES {
+        let hit = obstacles
+            .iter()
+            .filter_map(|obstacle| obstacle.sweep(position, remaining, radius))
+            .min_by(|a, b| a.0.total_cmp(&b.0));
+        let Some((t, normal)) = hit else {
+            position += remaining;
+            break;
+        };
+        position += t * remaining + SKIN * normal;
+        remaining *= 1.0 - t;
+        remaining -= normal * remaining.dot(normal).min(0.0);
+        velocity -= normal * velocity.dot(normal).min(0.0);
+    }
+    (position, velocity)
+}
+
+fn draw_obstacles(obstacles: Query<(&Obstacle, &Transform)>, mut gizmos: Gizmos) {
+    for (obstacle, transform) in &obstacles {
+        match obstacle.place(transform) {
+            PlacedObstacle::Circle { centre, radius } => {
+                gizmos.circle_2d(centre, radius, tailwind::STONE_300);
+            }
+            PlacedObstacle::Polygon(vertices) => {
+                gizmos.linestrip_2d(
+                    vertices.iter().chain(vertices.first()).copied(),
+                    tailwind::STONE_300,
+                );
+            }
+        }
+    }
+}
@@ -4,48 +4,198 @@ pub use material::*;
 use std::f32::consts::PI;
 
 use bevy::{color::palettes::tailwind, prelude::*};
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    PausableSystems, PlayMode,
+    input::{Action, KeyBindings},
+    level::LevelRoot,
     player::{Player, TrackingCameras},
-    top_down::movement,
+    top_down::{collision::Obstacle, movement},
 };
 
-pub fn plugin(app: &mut App) {
-    app.add_plugins(material::plugin);
+/// Tilts each player to match the ground beneath it, and builds the walls
+/// of grounds that have them.
+pub(crate) fn plugin(app: &mut App) {
     app.register_type::<Ground>();
+    app.init_resource::<Ground>();
+
+    app.add_systems(
+        Update,
+        (
+            build_walls.before(movement::apply_movement),
+            move_along_ground.after(movement::apply_movement),
+        )
+            .run_if(in_state(PlayMode::TopDown)),
+    );
+}
+
+/// Switches, draws and follows the ground.
+pub(crate) fn presentation_plugin(app: &mut App) {
+    app.add_plugins(material::plugin);
 
     app.add_systems(
         Update,
-        (swap_ground, move_along_ground, move_camera, draw_ground)
-            .chain()
-            .after(movement::apply_movement),
+        (
+            swap_ground
+                .in_set(PausableSystems)
+                .before(move_along_ground),
+            (move_camera, draw_ground).chain().after(move_along_ground),
+        )
+            .run_if(in_state(PlayMode::TopDown)),
     );
 }
 
 #[derive(Component, Reflect, Default)]
 pub struct GroundRotation(Quat);
 
-#[derive(Resource, Reflect)]
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(clap::ValueEnum))]
 pub enum Ground {
+    #[default]
     FlatPeriodic,
     Hills,
+    /// Flat, with walled rooms joined by a corridor.
+    Rooms,
+    /// Flat, with the obstacles and surfaces of the top-down level file.
+    Tiles,
 }
 
-fn swap_ground(mut ground: ResMut<Ground>, input: Res<ButtonInput<KeyCode>>) {
-    if input.just_pressed(KeyCode::KeyC) {
-        *ground = match *ground {
+impl Ground {
+    /// The ground to switch to when cycling through grounds.
+    pub fn next(self) -> Self {
+        match self {
             Ground::FlatPeriodic => Ground::Hills,
+            Ground::Hills => Ground::Rooms,
+            Ground::Rooms => Ground::Tiles,
+            Ground::Tiles => Ground::FlatPeriodic,
+        }
+    }
+
+    /// The ground before this one when cycling through grounds.
+    pub fn previous(self) -> Self {
+        match self {
+            Ground::FlatPeriodic => Ground::Tiles,
             Ground::Hills => Ground::FlatPeriodic,
+            Ground::Rooms => Ground::Hills,
+            Ground::Tiles => Ground::Rooms,
+        }
+    }
+}
+
+impl std::fmt::Display for Ground {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ground::FlatPeriodic => write!(f, "Flat"),
+            Ground::Hills => write!(f, "Hills"),
+            Ground::Rooms => write!(f, "Rooms"),
+            Ground::Tiles => write!(f, "Tiles"),
+        }
+    }
+}
+
+fn swap_ground(
+    mut ground: ResMut<Ground>,
+    input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+) {
+    if bindings.just_pressed(Action::CycleGround, &input) {
+        *ground = ground.next();
+    }
+}
+
+/// Marks an obstacle that belongs to the ground rather than the level.
+#[derive(Component, Debug)]
+struct Wall;
+
+/// Swaps in the walls of the current ground whenever it or the level changes.
+fn build_walls(
+    mut commands: Commands,
+    ground: Res<Ground>,
+    levels: Query<Entity, With<LevelRoot>>,
+    new_levels: Query<(), Added<LevelRoot>>,
+    walls: Query<Entity, With<Wall>>,
+) {
+    if !ground.is_changed() && new_levels.is_empty() {
+        return;
+    }
+    for wall in &walls {
+        commands.entity(wall).despawn();
+    }
+    if *ground != Ground::Rooms {
+        return;
+    }
+    for level in &levels {
+        for (position, obstacle) in rooms() {
+            commands.spawn((
+                Name::new("Wall"),
+                Wall,
+                obstacle,
+                Transform::from_translation(position.extend(0.0)),
+                ChildOf(level),
+            ));
         }
     }
 }
 
+/// A room around the origin, a corridor east out of it and a smaller room at
+/// the far end, with a few obstacles to steer around.
+fn rooms() -> Vec<(Vec2, Obstacle)> {
+    let wall = |x: f32, y: f32, width: f32, height: f32| {
+        (
+            Vec2::new(x, y),
+            Obstacle::Aabb(Rectangle::new(width, height)),
+        )
+    };
+    vec![
+        // The main room, with a doorway east.
+        wall(0.0, 420.0, 1280.0, 40.0),
+        wall(0.0, -420.0, 1280.0, 40.0),
+        wall(-620.0, 0.0, 40.0, 880.0),
+        wall(620.0, 260.0, 40.0, 360.0),
+        wall(620.0, -260.0, 40.0, 360.0),
+        // The corridor.
+        wall(1040.0, 100.0, 800.0, 40.0),
+        wall(1040.0, -100.0, 800.0, 40.0),
+        // The far room, with a doorway west.
+        wall(1460.0, 190.0, 40.0, 220.0),
+        wall(1460.0, -190.0, 40.0, 220.0),
+        wall(1680.0, 300.0, 480.0, 40.0),
+        wall(1680.0, -300.0, 480.0, 40.0),
+        wall(1900.0, 0.0, 40.0, 640.0),
+        // Things to steer around.
+        wall(-250.0, -250.0, 120.0, 80.0),
+        (
+            Vec2::new(-300.0, 180.0),
+            Obstacle::Circle(Circle::new(60.0)),
+        ),
+        (
+            Vec2::new(300.0, -200.0),
+            Obstacle::ConvexPolygon(vec![
+                Vec2::new(-70.0, -60.0),
+                Vec2::new(70.0, -60.0),
+                Vec2::new(0.0, 60.0),
+            ]),
+        ),
+        (
+            Vec2::new(1680.0, 0.0),
+            Obstacle::ConvexPolygon(vec![
+                Vec2::new(0.0, -60.0),
+                Vec2::new(60.0, 0.0),
+                Vec2::new(0.0, 60.0),
+                Vec2::new(-60.0, 0.0),
+            ]),
+        ),
+    ]
+}
+
 pub fn move_along_ground(
     mut objects: Query<(&mut Transform, &mut GroundRotation)>,
     ground: Res<Ground>,
 ) {
     match *ground {
-        Ground::FlatPeriodic => {}
+        Ground::FlatPeriodic | Ground::Rooms | Ground::Tiles => {}
         Ground::Hills => {
             for (mut tf, mut gr) in &mut objects {
                 let kx = 2.0 * PI / 1000.0;
@@ -105,7 +255,8 @@ pub fn move_camera(
                 }
             }
         }
-        Ground::Hills => {
+        // Follow the player smoothly where the view isn't one repeating tile.
+        Ground::Hills | Ground::Rooms | Ground::Tiles => {
             for (player_transform, its_cameras) in &players {
                 for camera in its_cameras.iter() {
                     if let Ok((mut camera_transform, Projection::Orthographic(proj))) =
@@ -152,7 +303,7 @@ pub fn draw_ground(
     mut gizmo: Gizmos,
 ) {
     match *ground {
-        Ground::FlatPeriodic => {}
+        Ground::FlatPeriodic | Ground::Rooms | Ground::Tiles => {}
         Ground::Hills => {
             for (tform, gr) in &players {
                 let pos = tform.translation;
@@ -10,7 +10,7 @@ use bevy::{
 /// This example uses a shader source file from the assets subdirectory
 const SHADER_ASSET_PATH: &str = "shaders/ground_material.wgsl";
 
-pub fn plugin(app: &mut App) {
+pub(super) fn plugin(app: &mut App) {
     app.add_plugins(Material2dPlugin::<GroundMaterial>::default());
 }
 
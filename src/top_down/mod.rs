@@ -1,11 +1,36 @@
 use bevy::prelude::*;
 
+pub mod collision;
 mod level;
-mod movement;
+pub mod movement;
+pub mod overlay;
+pub mod path;
 pub mod player;
+pub mod surface;
 
 pub use level::{Ground, GroundMaterial};
 
-pub(super) fn plugin(app: &mut App) {
-    app.add_plugins((movement::plugin, player::plugin, level::plugin));
+/// The top-down controller, its ground, obstacles, surfaces and path
+/// following, without any input or drawing.
+pub(crate) fn simulation_plugin(app: &mut App) {
+    app.add_plugins((
+        movement::plugin,
+        collision::plugin,
+        level::plugin,
+        path::plugin,
+        surface::plugin,
+    ));
+}
+
+/// Keyboard control, camera, ground, obstacle, surface and path drawing and
+/// the decomposition overlay for top-down mode.
+pub(crate) fn presentation_plugin(app: &mut App) {
+    app.add_plugins((
+        overlay::plugin,
+        player::plugin,
+        collision::presentation_plugin,
+        level::presentation_plugin,
+        path::presentation_plugin,
+        surface::presentation_plugin,
+    ));
 }
@@ -1,50 +1,130 @@
 use bevy::prelude::*;
 
 use crate::{
-    AppSystems, MotionParameters, player::MovementIntent, top_down::level::GroundRotation,
+    AppSystems, MotionParameters, PausableSystems, PlayMode,
+    player::{Controller, MotionRegime, MovementIntent, RegimeChanged, update_regime},
+    top_down::{
+        collision::{self, CircleCollider, Obstacle},
+        level::GroundRotation,
+        surface::{self, Surface},
+    },
 };
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<MovementController>();
 
-    app.add_systems(Update, apply_movement.in_set(AppSystems::Update));
+    app.add_systems(
+        Update,
+        apply_movement
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(PlayMode::TopDown)),
+    );
 }
 
 #[derive(Component, Reflect, Default)]
 #[reflect(Component)]
-#[require(MovementIntent, GroundRotation)]
+#[require(MovementIntent, MotionRegime, GroundRotation)]
 pub struct MovementController {
     velocity: Vec2,
 }
 
-pub(super) fn apply_movement(
+impl MovementController {
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    pub fn set_velocity(&mut self, velocity: Vec2) {
+        self.velocity = velocity;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.velocity.length()
+    }
+}
+
+impl Controller for MovementController {
+    fn planar_velocity(&self) -> Vec2 {
+        self.velocity
+    }
+}
+
+/// Splits `velocity` into the components along and across `intent_direction`,
+/// which [`apply_movement`] damps separately.
+pub fn decompose(velocity: Vec2, intent_direction: Vec2) -> (Vec2, Vec2) {
+    let longitudinal_velocity = intent_direction * intent_direction.dot(velocity);
+    (longitudinal_velocity, velocity - longitudinal_velocity)
+}
+
+/// Advances a velocity by `dt` seconds towards `intent`, damping the parts
+/// along and across the intent separately. Returns the new velocity and the
+/// damping regime that produced it.
+pub fn step(
+    velocity: Vec2,
+    intent: Vec2,
+    dt: f32,
+    params: &MotionParameters,
+) -> (Vec2, MotionRegime) {
+    let scaled_timestep = dt / params.t_acc;
+    let Some(intent_direction) = intent.try_normalize() else {
+        return (
+            velocity / (1.0 + params.alpha_stop * scaled_timestep),
+            MotionRegime::Stopping,
+        );
+    };
+    let target_velocity = params.max_speed * intent;
+    let (longitudinal_velocity, transverse_velocity) = decompose(velocity, intent_direction);
+    let longitudinal_speed = intent_direction.dot(longitudinal_velocity);
+    let (alpha_longitudinal, regime) = match longitudinal_speed {
+        vel if vel < 0.0 => (params.alpha_rev, MotionRegime::Reversing),
+        0.0 => (params.alpha_stop, MotionRegime::Stopping),
+        vel if vel > transverse_velocity.length() => (1.0, MotionRegime::Accelerating),
+        vel if vel > 0.0 => (1.0, MotionRegime::Turning),
+        _ => (1.0, MotionRegime::Accelerating),
+    };
+    let new_long_velocity = (longitudinal_velocity + scaled_timestep * target_velocity)
+        / (1.0 + alpha_longitudinal * scaled_timestep);
+
+    let new_trans_velocity = transverse_velocity / (1.0 + params.alpha_turn * scaled_timestep);
+    (new_long_velocity + new_trans_velocity, regime)
+}
+
+pub fn apply_movement(
     time: Res<Time>,
-    mut movement_query: Query<(&mut MovementController, &MovementIntent, &mut Transform)>,
+    mut movement_query: Query<
+        (
+            Entity,
+            &mut MovementController,
+            &mut MotionRegime,
+            &MovementIntent,
+            &mut Transform,
+            Option<&CircleCollider>,
+        ),
+        (Without<Obstacle>, Without<Surface>),
+    >,
+    obstacles: Query<(&Obstacle, &Transform)>,
+    surfaces: Query<(&Surface, &Transform)>,
     params: Res<MotionParameters>,
+    mut regime_events: EventWriter<RegimeChanged>,
 ) {
-    for (mut controller, intent, mut transform) in &mut movement_query {
-        let scaled_timestep = time.delta_secs() / params.t_acc;
-        if let Some(intent_direction) = intent.0.try_normalize() {
-            let target_velocity = params.max_speed * intent.0;
-            let longitudinal_speed = intent_direction.dot(controller.velocity);
-            let longitudinal_velocity = intent_direction * longitudinal_speed;
-            let transverse_velocity = controller.velocity - longitudinal_velocity;
-            let alpha_longitudinal = match longitudinal_speed {
-                vel if vel < 0.0 => params.alpha_rev,
-                0.0 => params.alpha_stop,
-                vel if vel > 0.0 => 1.0,
-                _ => 1.0,
-            };
-            let new_long_velocity = (longitudinal_velocity + scaled_timestep * target_velocity)
-                / (1.0 + alpha_longitudinal * scaled_timestep);
-
-            let new_trans_velocity =
-                transverse_velocity / (1.0 + params.alpha_turn * scaled_timestep);
-            controller.velocity = new_long_velocity + new_trans_velocity;
-        } else {
-            controller.velocity /= 1.0 + params.alpha_stop * scaled_timestep;
-        }
-
-        transform.translation += controller.velocity.extend(0.0) * time.delta_secs();
+    let obstacles: Vec<_> = obstacles
+        .iter()
+        .map(|(obstacle, transform)| obstacle.place(transform))
+        .collect();
+    let dt = time.delta_secs();
+    for (entity, mut controller, regime, intent, mut transform, collider) in &mut movement_query {
+        let position = transform.translation.xy();
+        let params = surface::tuning_at(&surfaces, &params, position);
+        let (velocity, new_regime) = step(controller.velocity, intent.0, dt, &params);
+        update_regime(entity, regime, new_regime, &mut regime_events);
+
+        let (position, velocity) = match collider {
+            Some(collider) => {
+                collision::move_and_slide(position, velocity, dt, collider.0.radius, &obstacles)
+            }
+            None => (position + velocity * dt, velocity),
+        };
+        controller.velocity = velocity;
+        transform.translation = position.extend(transform.translation.z);
     }
 }
@@ -0,0 +1,154 @@
+//! A debug overlay that draws the intent and velocity of each top-down
+//! controller, with the velocity split into the longitudinal and transverse
+//! parts that are damped by different alphas.
+
+use bevy::{color::palettes::tailwind, prelude::*};
+
+use crate::{
+    MotionParameters, PlayMode,
+    input::{Action, action_just_pressed},
+    player::MovementIntent,
+    top_down::movement::{self, MovementController, decompose},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DecompositionOverlay>();
+    app.init_resource::<DecompositionOverlay>();
+
+    app.add_observer(spawn_labels);
+    app.add_systems(
+        Update,
+        (
+            toggle_overlay.run_if(action_just_pressed(Action::ToggleVectors)),
+            (draw_arrows, update_labels).after(movement::apply_movement),
+        )
+            .run_if(in_state(PlayMode::TopDown)),
+    );
+}
+
+/// Arrow length per unit of speed, so that `max_speed` stays on screen.
+const ARROW_SCALE: f32 = 0.2;
+
+/// Whether the velocity decomposition overlay is shown, toggled with [`Action::ToggleVectors`].
+#[derive(Resource, Reflect, Debug, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct DecompositionOverlay(pub bool);
+
+fn toggle_overlay(mut overlay: ResMut<DecompositionOverlay>) {
+    overlay.0 = !overlay.0;
+}
+
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+enum Arrow {
+    Intent,
+    Velocity,
+    Longitudinal,
+    Transverse,
+}
+
+impl Arrow {
+    const ALL: [Self; 4] = [
+        Self::Intent,
+        Self::Velocity,
+        Self::Longitudinal,
+        Self::Transverse,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Intent => "target",
+            Self::Velocity => "v",
+            Self::Longitudinal => "v_long",
+            Self::Transverse => "v_trans",
+        }
+    }
+
+    fn colour(self) -> Srgba {
+        match self {
+            Self::Intent => tailwind::GRAY_300,
+            Self::Velocity => tailwind::YELLOW_300,
+            Self::Longitudinal => tailwind::GREEN_400,
+            Self::Transverse => tailwind::FUCHSIA_400,
+        }
+    }
+
+    /// The vector this arrow represents, in world units per second.
+    fn vector(self, velocity: Vec2, intent: Vec2, params: &MotionParameters) -> Vec2 {
+        let (longitudinal, transverse) = match intent.try_normalize() {
+            Some(direction) => decompose(velocity, direction),
+            None => (Vec2::ZERO, Vec2::ZERO),
+        };
+        match self {
+            Self::Intent => params.max_speed * intent,
+            Self::Velocity => velocity,
+            Self::Longitudinal => longitudinal,
+            Self::Transverse => transverse,
+        }
+    }
+}
+
+fn spawn_labels(trigger: Trigger<OnAdd, MovementController>, mut commands: Commands) {
+    commands.entity(trigger.target()).with_children(|parent| {
+        for arrow in Arrow::ALL {
+            parent.spawn((
+                Name::new("Decomposition Label"),
+                arrow,
+                Text2d::default(),
+                TextFont::from_font_size(14.0),
+                TextColor(arrow.colour().into()),
+                Transform::from_xyz(0.0, 0.0, 1.0),
+                Visibility::Hidden,
+            ));
+        }
+    });
+}
+
+fn draw_arrows(
+    overlay: Res<DecompositionOverlay>,
+    params: Res<MotionParameters>,
+    controllers: Query<(&MovementController, &MovementIntent, &Transform)>,
+    mut gizmos: Gizmos,
+) {
+    if !overlay.0 {
+        return;
+    }
+    for (controller, intent, transform) in &controllers {
+        let origin = transform.translation.xy();
+        for arrow in Arrow::ALL {
+            let vector = arrow.vector(controller.velocity(), intent.0, &params);
+            if vector != Vec2::ZERO {
+                gizmos.arrow_2d(origin, origin + ARROW_SCALE * vector, arrow.colour());
+            }
+        }
+    }
+}
+
+fn update_labels(
+    overlay: Res<DecompositionOverlay>,
+    params: Res<MotionParameters>,
+    controllers: Query<(&MovementController, &MovementIntent)>,
+    mut labels: Query<(
+        &Arrow,
+        &ChildOf,
+        &mut Text2d,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    for (arrow, child_of, mut text, mut transform, mut visibility) in &mut labels {
+        let Ok((controller, intent)) = controllers.get(child_of.parent()) else {
+            continue;
+        };
+        let vector = arrow.vector(controller.velocity(), intent.0, &params);
+        if !overlay.0 || vector == Vec2::ZERO {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        *visibility = Visibility::Inherited;
+        // Sit just beyond the tip of the arrow.
+        let tip = ARROW_SCALE * vector;
+        transform.translation = (tip + 16.0 * tip.normalize_or_zero()).extend(1.0);
+        text.0 = format!("{} {:.0}", arrow.name(), vector.length());
+    }
+}
@@ -0,0 +1,175 @@
+//! Steer a top-down controller along a list of waypoints, turning and braking
+//! early enough for its tuning that it doesn't overshoot.
+
+use bevy::{color::palettes::tailwind, prelude::*};
+
+use crate::{
+    AppSystems, MotionParameters, PausableSystems, PlayMode,
+    player::MovementIntent,
+    top_down::{
+        movement::{self, MovementController},
+        surface::{self, Surface},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<PathFollower>();
+
+    // Run after input is recorded, so that a path overrides the keyboard.
+    app.add_systems(
+        Update,
+        follow_paths
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .before(movement::apply_movement)
+            .run_if(in_state(PlayMode::TopDown)),
+    );
+}
+
+/// Draws the rest of each path.
+pub(super) fn presentation_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        draw_paths
+            .after(movement::apply_movement)
+            .run_if(in_state(PlayMode::TopDown)),
+    );
+}
+
+/// Drives [`MovementIntent`] through each waypoint in turn. The controller
+/// lets go as soon as its [stopping distance](MotionParameters::stopping_distance)
+/// reaches the last waypoint, and heads for the next waypoint as soon as its
+/// [turning distance](MotionParameters::turning_distance) reaches the current
+/// one. Once finished, the intent is left alone.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[reflect(Component)]
+#[require(MovementIntent)]
+pub struct PathFollower {
+    waypoints: Vec<Vec2>,
+    next: usize,
+    /// How close to the last waypoint counts as having arrived.
+    pub arrival_radius: f32,
+    /// Where the segment being followed starts.
+    previous: Option<Vec2>,
+    tracking_error: f32,
+}
+
+impl PathFollower {
+    pub fn new(waypoints: impl IntoIterator<Item = Vec2>) -> Self {
+        Self {
+            waypoints: waypoints.into_iter().collect(),
+            next: 0,
+            arrival_radius: 5.0,
+            previous: None,
+            tracking_error: 0.0,
+        }
+    }
+
+    pub fn with_arrival_radius(mut self, arrival_radius: f32) -> Self {
+        self.arrival_radius = arrival_radius;
+        self
+    }
+
+    pub fn waypoints(&self) -> &[Vec2] {
+        &self.waypoints
+    }
+
+    /// The waypoints not yet reached, starting with the one being headed for.
+    pub fn remaining(&self) -> &[Vec2] {
+        &self.waypoints[self.next.min(self.waypoints.len())..]
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.waypoints.len()
+    }
+
+    /// How far the controller was from the segment it's following, as of the
+    /// last update.
+    pub fn tracking_error(&self) -> f32 {
+        self.tracking_error
+    }
+}
+
+fn follow_paths(
+    params: Res<MotionParameters>,
+    surfaces: Query<(&Surface, &Transform)>,
+    mut followers: Query<(
+        &mut PathFollower,
+        &mut MovementIntent,
+        &MovementController,
+        &Transform,
+    )>,
+) {
+    for (mut path, mut intent, controller, transform) in &mut followers {
+        if path.is_finished() {
+            continue;
+        }
+        let position = transform.translation.xy();
+        let velocity = controller.velocity();
+        let params = surface::tuning_at(&surfaces, &params, position);
+        path.previous.get_or_insert(position);
+
+        // Head for the next waypoint once the turn towards it would carry the
+        // controller through the current one anyway.
+        while path.next + 1 < path.waypoints.len() {
+            let current = path.waypoints[path.next];
+            let Some(next_direction) = (path.waypoints[path.next + 1] - current).try_normalize()
+            else {
+                path.next += 1;
+                continue;
+            };
+            let across = velocity.reject_from_normalized(next_direction).length();
+            let distance = position.distance(current);
+            if distance > params.turning_distance(across).max(path.arrival_radius) {
+                break;
+            }
+            path.previous = Some(current);
+            path.next += 1;
+        }
+        let target = path.waypoints[path.next];
+        let from = path.previous.unwrap_or(position);
+        path.tracking_error = distance_to_segment(position, from, target);
+
+        let offset = target - position;
+        if path.next + 1 == path.waypoints.len() {
+            if offset.length() <= path.arrival_radius {
+                path.next += 1;
+                intent.0 = Vec2::ZERO;
+                continue;
+            }
+            // Let go once coasting would carry the controller to the target.
+            let closing_speed = velocity.dot(offset.normalize());
+            if closing_speed > 0.0 && params.stopping_distance(closing_speed) >= offset.length() {
+                intent.0 = Vec2::ZERO;
+                continue;
+            }
+        }
+        intent.0 = offset.normalize_or_zero();
+    }
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let along = if segment.length_squared() > 0.0 {
+        ((point - start).dot(segment) / segment.length_squared()).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    point.distance(start + along * segment)
+}
+
+fn draw_paths(followers: Query<(&PathFollower, &Transform)>, mut gizmos: Gizmos) {
+    for (path, transform) in &followers {
+        if path.is_finished() {
+            continue;
+        }
+        let position = transform.translation.xy();
+        gizmos.linestrip_2d(
+            std::iter::once(position).chain(path.remaining().iter().copied()),
+            tailwind::SKY_300.with_alpha(0.6),
+        );
+        for &waypoint in path.remaining() {
+            gizmos.circle_2d(waypoint, path.arrival_radius.max(4.0), tailwind::SKY_300);
+        }
+    }
+}
@@ -1,75 +1,121 @@
 use bevy::{color::palettes::tailwind, prelude::*, render::camera::ScalingMode};
 
 use crate::{
-    AppSystems,
-    player::{self, MovementIntent, Player, TrackingCameras},
-    top_down::{level::GroundMaterial, movement::MovementController},
+    AppSystems, PausableSystems, PlayMode,
+    animation::{PlayerAssets, top_down_sprite},
+    input::{Action, KeyBindings},
+    motion_audio::Footsteps,
+    npc::{Behaviour, Npc},
+    player::{CameraOf, MovementIntent, Player},
+    screens::Screen,
+    top_down::{collision::CircleCollider, level::GroundMaterial, movement::MovementController},
+    trail::MotionTrail,
 };
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_plugins(player::plugin);
-
     app.add_systems(
         Update,
-        record_player_directional_input.in_set(AppSystems::RecordInput),
+        record_player_directional_input
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems)
+            .run_if(in_state(PlayMode::TopDown)),
+    );
+    app.add_systems(
+        Update,
+        attach_ground_backdrop.run_if(in_state(PlayMode::TopDown)),
     );
 }
 
-pub fn player(
-    meshes: &mut Assets<Mesh>,
-    mats: &mut Assets<ColorMaterial>,
-    ground_mat: &mut Assets<GroundMaterial>,
-) -> impl Bundle {
-    let mesh = Circle::new(10.0).mesh().build();
-    let player_mesh = meshes.add(mesh);
-    let player_colour = mats.add(Color::from(tailwind::BLUE_400));
-
-    let bg_mesh = meshes.add(Rectangle::new(1000.0, 1000.0).mesh().build());
-    let bg_mat = ground_mat.add(tailwind::RED_500);
-
+pub fn player(player_assets: &PlayerAssets) -> impl Bundle {
     (
         Name::new("Player"),
         Player,
         MovementController::default(),
-        Mesh2d(player_mesh),
-        MeshMaterial2d(player_colour),
+        CircleCollider(Circle::new(12.0)),
+        Footsteps::default(),
+        MotionTrail::default(),
+        children![top_down_sprite(player_assets)],
+        Visibility::default(),
         Transform::default(),
-        related!(
-            TrackingCameras[(
-                Name::new("Camera"),
-                Camera2d,
-                Projection::Orthographic(OrthographicProjection {
-                    scaling_mode: ScalingMode::FixedHorizontal {
-                        viewport_width: 1000.0,
-                    },
-                    ..OrthographicProjection::default_2d()
-                }),
-                children![(
-                    Transform::from_xyz(0.0, 0.0, -10.0),
-                    Mesh2d(bg_mesh),
-                    MeshMaterial2d(bg_mat)
-                )]
-            )]
-        ),
     )
 }
 
-fn record_player_directional_input(
+/// A top-down character driven by `behaviour`, starting at `position`.
+pub fn npc(player_assets: &PlayerAssets, position: Vec2, behaviour: Behaviour) -> impl Bundle {
+    (
+        Name::new("NPC"),
+        Npc,
+        behaviour,
+        MovementController::default(),
+        CircleCollider(Circle::new(12.0)),
+        children![top_down_sprite(player_assets)],
+        Visibility::default(),
+        Transform::from_translation(position.extend(0.0)),
+    )
+}
+
+/// A camera to follow a top-down player.
+pub fn camera() -> impl Bundle {
+    (
+        Name::new("Camera"),
+        Camera2d,
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::FixedHorizontal {
+                viewport_width: 1000.0,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+    )
+}
+
+/// The ground drawn behind a camera that follows a player, carried along
+/// with the camera so that it always fills the view.
+#[derive(Component, Debug)]
+struct GroundBackdrop;
+
+/// Gives each camera that follows a player, whether spawned with the level or
+/// by the app, a ground backdrop of its own.
+fn attach_ground_backdrop(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut ground_mats: ResMut<Assets<GroundMaterial>>,
+    cameras: Query<Entity, (With<Camera>, With<CameraOf>)>,
+    backdrops: Query<&ChildOf, With<GroundBackdrop>>,
+) {
+    for camera in &cameras {
+        if backdrops.iter().any(|child_of| child_of.parent() == camera) {
+            continue;
+        }
+        commands.spawn((
+            Name::new("Ground Backdrop"),
+            GroundBackdrop,
+            Transform::from_xyz(0.0, 0.0, -10.0),
+            Mesh2d(meshes.add(Rectangle::new(1000.0, 1000.0).mesh().build())),
+            MeshMaterial2d(ground_mats.add(tailwind::RED_500)),
+            ChildOf(camera),
+            StateScoped(Screen::Gameplay),
+            StateScoped(PlayMode::TopDown),
+        ));
+    }
+}
+
+pub(crate) fn record_player_directional_input(
     input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
     mut intent_query: Query<&mut MovementIntent, With<Player>>,
 ) {
     let mut intent = Vec2::ZERO;
 
-    if input.pressed(KeyCode::KeyW) || input.pressed(KeyCode::ArrowUp) {
+    if bindings.pressed(Action::MoveUp, &input) {
         intent.y += 1.0;
     }
-    if input.pressed(KeyCode::KeyS) || input.pressed(KeyCode::ArrowDown) {
+    if bindings.pressed(Action::MoveDown, &input) {
         intent.y -= 1.0;
     }
-    if input.pressed(KeyCode::KeyA) || input.pressed(KeyCode::ArrowLeft) {
+    if bindings.pressed(Action::MoveLeft, &input) {
         intent.x -= 1.0;
     }
-    if input.pressed(KeyCode::KeyD) || input.pressed(KeyCode::ArrowRight) {
+    if bindings.pressed(Action::MoveRight, &input) {
         intent.x += 1.0;
     }
 
@@ -0,0 +1,68 @@
+//! Regions of the ground with less grip than the rest, such as ice, where
+//! top-down controllers take longer to stop, turn and reverse.
+
+use bevy::{color::palettes::tailwind, prelude::*};
+
+use crate::{MotionParameters, PlayMode};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Surface>();
+}
+
+/// Outlines every surface.
+pub(super) fn presentation_plugin(app: &mut App) {
+    app.add_systems(Update, draw_surfaces.run_if(in_state(PlayMode::TopDown)));
+}
+
+/// A rectangle of ground `half_size` either side of the entity's [`Transform`]
+/// whose damping is scaled by `grip`.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[reflect(Component)]
+#[require(Transform)]
+pub struct Surface {
+    pub half_size: Vec2,
+    /// One for ordinary ground, and less for ground that lets controllers
+    /// slide further.
+    pub grip: f32,
+}
+
+impl Surface {
+    pub fn contains(&self, transform: &Transform, position: Vec2) -> bool {
+        let offset = position - transform.translation.xy();
+        offset.abs().cmple(self.half_size).all()
+    }
+
+    /// The tuning `params` becomes on this surface. Use [`tuning_at`] to find
+    /// the tuning wherever a controller happens to be.
+    pub fn tuning(&self, params: &MotionParameters) -> MotionParameters {
+        MotionParameters {
+            alpha_stop: params.alpha_stop * self.grip,
+            alpha_rev: params.alpha_rev * self.grip,
+            alpha_turn: params.alpha_turn * self.grip,
+            ..*params
+        }
+    }
+}
+
+/// The tuning in effect at `position`: `params` as changed by the surface
+/// there, if any.
+pub fn tuning_at(
+    surfaces: &Query<(&Surface, &Transform)>,
+    params: &MotionParameters,
+    position: Vec2,
+) -> MotionParameters {
+    surfaces
+        .iter()
+        .find(|(surface, transform)| surface.contains(transform, position))
+        .map_or(*params, |(surface, _)| surface.tuning(params))
+}
+
+fn draw_surfaces(surfaces: Query<(&Surface, &Transform)>, mut gizmos: Gizmos) {
+    for (surface, transform) in &surfaces {
+        gizmos.rect_2d(
+            transform.translation.xy(),
+            2.0 * surface.half_size,
+            tailwind::SKY_200,
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! A frame-by-frame log of each player's motion, for inspecting and exporting
+//! simulated runs.
+
+use std::fmt::Write;
+
+use bevy::{diagnostic::FrameCount, prelude::*};
+
+use crate::{
+    player::{Controller, MotionRegime, Player},
+    screens::Screen,
+    side_scroll::movement::BasicMovementController,
+    top_down::movement::MovementController,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    // Record after transform propagation so that every mode's ground mapping
+    // has already placed the player for this frame.
+    app.add_systems(
+        PostUpdate,
+        (
+            record_trace::<BasicMovementController>,
+            record_trace::<MovementController>,
+        )
+            .after(TransformSystem::TransformPropagate)
+            .run_if(resource_exists::<MotionTrace>.and(in_state(Screen::Gameplay))),
+    );
+}
+
+/// Every player's motion on each gameplay frame, recorded while this resource exists.
+#[derive(Resource, Debug, Default)]
+pub struct MotionTrace {
+    pub samples: Vec<TraceSample>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TraceSample {
+    pub frame: u32,
+    /// Seconds of simulation time since startup.
+    pub time: f32,
+    pub position: Vec2,
+    /// The controller's velocity, as reported by [`Controller::planar_velocity`].
+    pub velocity: Vec2,
+    pub regime: MotionRegime,
+}
+
+impl MotionTrace {
+    /// The samples as CSV, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("frame,time,x,y,vx,vy,speed,regime\n");
+        for sample in &self.samples {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{:?}",
+                sample.frame,
+                sample.time,
+                sample.position.x,
+                sample.position.y,
+                sample.velocity.x,
+                sample.velocity.y,
+                sample.velocity.length(),
+                sample.regime,
+            );
+        }
+        csv
+    }
+}
+
+fn record_trace<C: Controller>(
+    frame: Res<FrameCount>,
+    time: Res<Time>,
+    players: Query<(&C, &GlobalTransform, &MotionRegime), With<Player>>,
+    mut trace: ResMut<MotionTrace>,
+) {
+    for (controller, transform, regime) in &players {
+        trace.samples.push(TraceSample {
+            frame: frame.0,
+            time: time.elapsed_secs(),
+            position: transform.translation().xy(),
+            velocity: controller.planar_velocity(),
+            regime: *regime,
+        });
+    }
+}
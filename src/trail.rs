@@ -0,0 +1,230 @@
+//! Fading motion trails and footprints showing where each player has been.
+
+use std::collections::VecDeque;
+
+use bevy::{color::palettes::tailwind, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MotionParameters,
+    input::{Action, action_just_pressed},
+    player::MotionRegime,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<MotionTrail>();
+    app.register_type::<TrailDisplay>();
+    app.init_resource::<TrailDisplay>();
+
+    app.add_systems(
+        Update,
+        cycle_trail_display.run_if(action_just_pressed(Action::CycleTrail)),
+    );
+    // Record after transform propagation so that every mode's ground mapping
+    // has already placed the player for this frame.
+    app.add_systems(
+        PostUpdate,
+        (record_trails, draw_trails)
+            .chain()
+            .after(TransformSystem::TransformPropagate),
+    );
+}
+
+/// How trails are coloured, cycled with [`Action::CycleTrail`].
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Resource)]
+pub enum TrailDisplay {
+    /// Blend from slow to fast relative to `max_speed`.
+    #[default]
+    Speed,
+    /// One colour per [`MotionRegime`].
+    Regime,
+    /// Don't draw trails at all.
+    Hidden,
+}
+
+impl TrailDisplay {
+    pub fn next(self) -> Self {
+        match self {
+            TrailDisplay::Speed => TrailDisplay::Regime,
+            TrailDisplay::Regime => TrailDisplay::Hidden,
+            TrailDisplay::Hidden => TrailDisplay::Speed,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            TrailDisplay::Speed => TrailDisplay::Hidden,
+            TrailDisplay::Regime => TrailDisplay::Speed,
+            TrailDisplay::Hidden => TrailDisplay::Regime,
+        }
+    }
+}
+
+fn cycle_trail_display(mut display: ResMut<TrailDisplay>) {
+    *display = display.next();
+}
+
+/// Records recent positions of an entity so they can be drawn as a fading trail.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct MotionTrail {
+    /// How long, in seconds, a sample stays visible.
+    pub lifetime: f32,
+    /// Distance travelled between footprints.
+    pub footprint_spacing: f32,
+    /// Sideways distance of each footprint from the path, alternating sides.
+    pub footprint_offset: f32,
+    #[reflect(ignore)]
+    samples: VecDeque<TrailSample>,
+    #[reflect(ignore)]
+    footprints: VecDeque<Footprint>,
+    distance_since_footprint: f32,
+    left_foot: bool,
+}
+
+impl Default for MotionTrail {
+    fn default() -> Self {
+        Self {
+            lifetime: 2.0,
+            footprint_spacing: 40.0,
+            footprint_offset: 6.0,
+            samples: VecDeque::new(),
+            footprints: VecDeque::new(),
+            distance_since_footprint: 0.0,
+            left_foot: false,
+        }
+    }
+}
+
+impl MotionTrail {
+    pub fn with_footprint_offset(mut self, footprint_offset: f32) -> Self {
+        self.footprint_offset = footprint_offset;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TrailSample {
+    position: Vec2,
+    speed: f32,
+    regime: MotionRegime,
+    time: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Footprint {
+    position: Vec2,
+    sample: TrailSample,
+}
+
+fn record_trails(
+    time: Res<Time>,
+    mut trails: Query<(&mut MotionTrail, &GlobalTransform, &MotionRegime)>,
+) {
+    let now = time.elapsed_secs();
+    let dt = time.delta_secs();
+    for (mut trail, transform, regime) in &mut trails {
+        let lifetime = trail.lifetime;
+        while trail
+            .samples
+            .front()
+            .is_some_and(|sample| now - sample.time > lifetime)
+        {
+            trail.samples.pop_front();
+        }
+        while trail
+            .footprints
+            .front()
+            .is_some_and(|footprint| now - footprint.sample.time > lifetime)
+        {
+            trail.footprints.pop_front();
+        }
+
+        let position = transform.translation().xy();
+        let displacement = trail
+            .samples
+            .back()
+            .map_or(Vec2::ZERO, |last| position - last.position);
+        // A paused clock gives no new information about where we've been.
+        if dt == 0.0 && !trail.samples.is_empty() {
+            continue;
+        }
+        let speed = if dt > 0.0 {
+            displacement.length() / dt
+        } else {
+            0.0
+        };
+        let sample = TrailSample {
+            position,
+            speed,
+            regime: *regime,
+            time: now,
+        };
+        trail.samples.push_back(sample);
+
+        trail.distance_since_footprint += displacement.length();
+        if trail.distance_since_footprint >= trail.footprint_spacing {
+            trail.distance_since_footprint = 0.0;
+            trail.left_foot = !trail.left_foot;
+            let side = if trail.left_foot { 1.0 } else { -1.0 };
+            let offset = displacement.normalize_or_zero().perp() * trail.footprint_offset * side;
+            trail.footprints.push_back(Footprint {
+                position: position + offset,
+                sample,
+            });
+        }
+    }
+}
+
+fn draw_trails(
+    time: Res<Time>,
+    display: Res<TrailDisplay>,
+    params: Res<MotionParameters>,
+    trails: Query<&MotionTrail>,
+    mut gizmos: Gizmos,
+) {
+    if *display == TrailDisplay::Hidden {
+        return;
+    }
+    let now = time.elapsed_secs();
+    let colour = |sample: &TrailSample, lifetime: f32| {
+        let base = match *display {
+            TrailDisplay::Regime => regime_colour(sample.regime),
+            _ => speed_colour(sample.speed / params.max_speed),
+        };
+        base.with_alpha((1.0 - (now - sample.time) / lifetime).clamp(0.0, 1.0))
+    };
+    for trail in &trails {
+        gizmos.linestrip_gradient_2d(
+            trail
+                .samples
+                .iter()
+                .map(|sample| (sample.position, colour(sample, trail.lifetime))),
+        );
+        for footprint in &trail.footprints {
+            gizmos.circle_2d(
+                footprint.position,
+                3.0,
+                colour(&footprint.sample, trail.lifetime),
+            );
+        }
+    }
+}
+
+fn speed_colour(fraction_of_max: f32) -> Color {
+    Color::from(tailwind::SKY_400).mix(
+        &Color::from(tailwind::ROSE_500),
+        fraction_of_max.clamp(0.0, 1.0),
+    )
+}
+
+fn regime_colour(regime: MotionRegime) -> Color {
+    match regime {
+        MotionRegime::Accelerating => tailwind::GREEN_400,
+        MotionRegime::Stopping => tailwind::AMBER_400,
+        MotionRegime::Reversing => tailwind::RED_500,
+        MotionRegime::Turning => tailwind::VIOLET_400,
+    }
+    .into()
+}
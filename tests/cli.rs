@@ -0,0 +1,9 @@
+//! The command line is put together without clashing or missing arguments.
+
+use char_motion_example::cli::Cli;
+use clap::CommandFactory;
+
+#[test]
+fn cli_is_well_formed() {
+    Cli::command().debug_assert();
+}
@@ -0,0 +1,65 @@
+//! Top-down colliders slide along obstacles instead of passing through them.
+
+use bevy::prelude::*;
+use char_motion_example::top_down::collision::{Obstacle, move_and_slide};
+
+const RADIUS: f32 = 12.0;
+
+#[test]
+fn fast_moves_stop_at_thin_walls_and_keep_sliding() {
+    let wall =
+        Obstacle::Aabb(Rectangle::new(4.0, 400.0)).place(&Transform::from_xyz(100.0, 0.0, 0.0));
+    // Fast enough to cross the wall several times over in one step.
+    let velocity = Vec2::new(3000.0, 600.0);
+    let (position, velocity) = move_and_slide(Vec2::ZERO, velocity, 0.1, RADIUS, &[wall]);
+
+    assert!(position.x <= 98.0 - RADIUS + 0.1, "{position}");
+    assert!(position.x >= 98.0 - RADIUS - 0.1, "{position}");
+    assert_eq!(velocity.x, 0.0);
+    // The whole sideways part of the move survives the hit.
+    assert!((velocity.y - 600.0).abs() < 1e-3, "{velocity}");
+    assert!((position.y - 60.0).abs() < 0.1, "{position}");
+}
+
+#[test]
+fn circles_and_polygons_block_from_any_side() {
+    let obstacles = [
+        Obstacle::Circle(Circle::new(50.0)).place(&Transform::default()),
+        Obstacle::ConvexPolygon(vec![
+            Vec2::new(0.0, 60.0),
+            Vec2::new(70.0, -60.0),
+            Vec2::new(-70.0, -60.0),
+        ])
+        .place(&Transform::from_xyz(400.0, 0.0, 0.0)),
+    ];
+    // How close each shape's centre can be approached: the circle's radius, and
+    // the distance from the triangle's centre to its slanted sides.
+    for (centre, clearance) in [(Vec2::ZERO, 50.0), (Vec2::new(400.0, 0.0), 30.2)] {
+        for angle in 0..16 {
+            let direction = Vec2::from_angle(angle as f32 / 16.0 * std::f32::consts::TAU);
+            let start = centre + 150.0 * direction;
+            let (position, _) = move_and_slide(start, -2000.0 * direction, 0.1, RADIUS, &obstacles);
+            assert!(
+                position.distance(centre) >= clearance + RADIUS - 0.1,
+                "passed into the obstacle at {centre} from {start}, reaching {position}"
+            );
+        }
+    }
+}
+
+#[test]
+fn overlapping_colliders_are_pushed_out() {
+    let pillar = Obstacle::Circle(Circle::new(50.0)).place(&Transform::default());
+    let (position, velocity) = move_and_slide(
+        Vec2::new(30.0, 0.0),
+        Vec2::new(-100.0, 0.0),
+        0.0,
+        RADIUS,
+        &[pillar],
+    );
+    assert!(
+        (position.length() - 50.0 - RADIUS).abs() < 1e-3,
+        "{position}"
+    );
+    assert_eq!(velocity, Vec2::ZERO);
+}
@@ -0,0 +1,19 @@
+//! Setup shared by the integration tests.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use char_motion_example::{MotionParameters, PlayMode, SimulationPlugins};
+
+/// An app with only the simulation, in `mode`, stepping a fixed 60 Hz with a
+/// basic tuning. Insert a [`MotionParameters`] to try another tuning.
+pub fn simulation_app(mode: PlayMode) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, SimulationPlugins));
+    app.insert_state(mode);
+    app.insert_resource(MotionParameters::full(500.0, 0.5, 5.0, 5.0));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+        1.0 / 60.0,
+    )));
+    app
+}
@@ -0,0 +1,107 @@
+//! Endless ground is the same for the same seed, keeps within its steepness,
+//! and is generated ahead of a runner and forgotten behind it.
+
+mod common;
+
+use bevy::prelude::*;
+use char_motion_example::{
+    PlayMode,
+    player::MovementIntent,
+    side_scroll::{
+        EndlessTerrain, Ground, GroundProfile,
+        endless::{CHUNK_WIDTH, POINTS_PER_CHUNK},
+        level::PositionAlongGround,
+        movement::BasicMovementController,
+    },
+};
+
+use common::simulation_app;
+
+#[test]
+fn the_same_seed_makes_the_same_ground() {
+    let terrain = EndlessTerrain {
+        seed: 7,
+        ..default()
+    };
+    let points = terrain.points(-3..3);
+    assert_eq!(points.len(), 6 * POINTS_PER_CHUNK);
+    assert_eq!(points[0].x, -3.0 * CHUNK_WIDTH);
+    // Chunks come out the same whichever order they're made in.
+    let backwards: Vec<_> = (-3..3)
+        .rev()
+        .flat_map(|index| terrain.chunk(index))
+        .collect();
+    let mut backwards_sorted = backwards.clone();
+    backwards_sorted.sort_by(|a, b| a.x.total_cmp(&b.x));
+    assert_eq!(backwards_sorted, points);
+    assert_eq!(terrain.points(-3..3), points);
+
+    let other = EndlessTerrain { seed: 8, ..terrain };
+    assert_ne!(other.points(-3..3), points);
+}
+
+#[test]
+fn ground_keeps_within_its_steepness() {
+    for (seed, steepness, bump_frequency) in [(0, 0.5, 2.0), (1, 0.2, 10.0), (2, 1.5, 0.5)] {
+        let terrain = EndlessTerrain {
+            seed,
+            steepness,
+            bump_frequency,
+        };
+        let points = terrain.points(-20..20);
+        let steepest = points
+            .windows(2)
+            .map(|pair| ((pair[1].y - pair[0].y) / (pair[1].x - pair[0].x)).abs())
+            .fold(0.0, f32::max);
+        assert!(steepest <= steepness + 1e-4, "{steepest} > {steepness}");
+        assert!(steepest > 0.1 * steepness, "seed {seed} is too flat");
+    }
+}
+
+#[test]
+fn ground_streams_around_a_runner() {
+    let mut app = simulation_app(PlayMode::SideScroll);
+    app.insert_resource(Ground::Endless);
+    let runner = app
+        .world_mut()
+        .spawn((
+            BasicMovementController::default(),
+            MovementIntent(Vec2::X),
+            PositionAlongGround(0.0),
+            Transform::default(),
+        ))
+        .id();
+
+    let mut most_points = 0;
+    for _ in 0..600 {
+        app.update();
+        most_points = most_points.max(app.world().resource::<GroundProfile>().points().len());
+    }
+    // Only the chunks around the runner are ever kept.
+    assert!(most_points <= 8 * POINTS_PER_CHUNK, "{most_points} points");
+
+    let position = app
+        .world()
+        .get::<Transform>(runner)
+        .unwrap()
+        .translation
+        .xy();
+    let profile = app.world().resource::<GroundProfile>();
+    let first = profile.points()[0];
+    assert!(position.x > 3000.0, "{position}");
+    assert!(first.x > 0.0, "the start was never forgotten");
+
+    // What's left is just what the seed makes there, and the runner is on it.
+    let start = (first.x / CHUNK_WIDTH).round() as i64;
+    let chunks = profile.points().len() / POINTS_PER_CHUNK;
+    let terrain = app.world().resource::<EndlessTerrain>();
+    assert_eq!(
+        profile.points(),
+        terrain.points(start..start + chunks as i64)
+    );
+    let ground = profile.placement(profile.arc_position_at(position.x)).0;
+    assert!(
+        (ground.y - position.y).abs() < 1e-2,
+        "{ground} vs {position}"
+    );
+}
@@ -0,0 +1,126 @@
+//! Round trips between tunings and the feel targets they're solved from.
+
+use char_motion_example::{
+    MotionParameters,
+    feel::{FeelTarget, UnreachableTarget},
+    sweep::top_down_metrics,
+};
+
+/// One frame of the 60 Hz manoeuvres, the resolution of every time metric.
+const FRAME: f32 = 1.0 / 60.0;
+
+fn assert_close(actual: Option<f32>, expected: f32, tolerance: f32, what: &str) {
+    let actual = actual.unwrap_or_else(|| panic!("{what} never finished"));
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{what}: expected {expected} ± {tolerance}, got {actual}"
+    );
+}
+
+#[test]
+fn tunings_reach_their_feel_targets() {
+    let targets = [
+        (500.0, 0.5, 100.0, 0.65, 60.0),
+        (500.0, 2.0, 800.0, 2.5, 300.0),
+        (300.0, 1.0, 50.0, 1.2, 250.0),
+        (800.0, 0.3, 2000.0, 0.4, 40.0),
+    ];
+    for (max_speed, time_to_90, stopping_distance, reversal_time, turn_radius) in targets {
+        let params = MotionParameters::from_feel_with_turn(
+            max_speed,
+            time_to_90,
+            stopping_distance,
+            reversal_time,
+            turn_radius,
+        )
+        .unwrap();
+        let metrics = top_down_metrics(&params);
+        // A frame's worth of movement either way is as close as whole frames allow.
+        let distance_tolerance = 0.01 * max_speed;
+        assert_close(metrics.time_to_90, time_to_90, FRAME, "time to 90%");
+        assert_close(
+            metrics.stopping_distance,
+            stopping_distance,
+            distance_tolerance,
+            "stopping distance",
+        );
+        assert_close(metrics.reversal_time, reversal_time, FRAME, "reversal time");
+        assert_close(
+            metrics.turn_radius,
+            turn_radius,
+            distance_tolerance,
+            "turn radius",
+        );
+    }
+}
+
+#[test]
+fn measured_feel_solves_to_an_equivalent_tuning() {
+    let tunings = [
+        MotionParameters::full_with_separate_turn(500.0, 1.0, 1.0, 1.0, 1.0),
+        MotionParameters::full_with_separate_turn(500.0, 1.0, 5.0, 5.0, 5.0),
+        MotionParameters::full_with_separate_turn(400.0, 0.5, 3.0, 2.0, 8.0),
+    ];
+    for tuning in tunings {
+        let metrics = top_down_metrics(&tuning);
+        let params = MotionParameters::from_feel_with_turn(
+            tuning.max_speed(),
+            metrics.time_to_90.unwrap(),
+            metrics.stopping_distance.unwrap(),
+            metrics.reversal_time.unwrap(),
+            metrics.turn_radius.unwrap(),
+        )
+        .unwrap();
+        // Reversal and stopping times are only measured to the frame, which
+        // leaves the alphas loosely pinned down, so compare how the tunings feel.
+        let solved = top_down_metrics(&params);
+        assert_eq!(params.max_speed(), tuning.max_speed());
+        assert!(
+            (params.t_acc() / tuning.t_acc() - 1.0).abs() < 0.02,
+            "{params:?}"
+        );
+        assert_close(
+            solved.time_to_90,
+            metrics.time_to_90.unwrap(),
+            FRAME,
+            "time to 90%",
+        );
+        assert_close(
+            solved.stopping_distance,
+            metrics.stopping_distance.unwrap(),
+            0.01 * tuning.max_speed(),
+            "stopping distance",
+        );
+        assert_close(
+            solved.reversal_time,
+            metrics.reversal_time.unwrap(),
+            FRAME,
+            "reversal time",
+        );
+        assert_close(
+            solved.turn_radius,
+            metrics.turn_radius.unwrap(),
+            0.01 * tuning.max_speed(),
+            "turn radius",
+        );
+    }
+}
+
+#[test]
+fn turning_defaults_to_reversing() {
+    let params = MotionParameters::from_feel(500.0, 1.0, 200.0, 1.2).unwrap();
+    assert_eq!(params.alpha_turn(), params.alpha_rev());
+}
+
+#[test]
+fn impossible_targets_are_reported() {
+    // Reversing includes accelerating back up from rest, so it can't be quicker.
+    assert_eq!(
+        MotionParameters::from_feel(500.0, 1.0, 200.0, 0.5),
+        Err(UnreachableTarget(FeelTarget::ReversalTime)),
+    );
+    assert_eq!(
+        MotionParameters::from_feel(500.0, -1.0, 200.0, 1.5),
+        Err(UnreachableTarget(FeelTarget::TimeTo90)),
+    );
+}
@@ -0,0 +1,128 @@
+//! Side-scroll characters stop at walls, and jump up through platforms to
+//! land on them.
+
+mod common;
+
+use bevy::prelude::*;
+use char_motion_example::{
+    PlayMode,
+    player::MovementIntent,
+    side_scroll::{
+        Ground, GroundProfile,
+        footing::{BODY_HALF_WIDTH, Footing, Platform, Wall},
+        level::PositionAlongGround,
+        movement::BasicMovementController,
+    },
+};
+
+use common::simulation_app;
+
+fn spawn_character(app: &mut App, velocity: f32, intent: Vec2) -> Entity {
+    let mut controller = BasicMovementController::default();
+    controller.set_velocity(velocity);
+    app.world_mut()
+        .spawn((
+            controller,
+            MovementIntent(intent),
+            PositionAlongGround(0.0),
+            Transform::default(),
+        ))
+        .id()
+}
+
+fn spawn_wall(app: &mut App, position: f32, restitution: f32) {
+    app.world_mut().spawn((
+        Wall {
+            height: 100.0,
+            restitution,
+        },
+        PositionAlongGround(position),
+    ));
+}
+
+fn position_along_ground(app: &App, entity: Entity) -> f32 {
+    app.world().get::<PositionAlongGround>(entity).unwrap().0
+}
+
+#[test]
+fn walls_stop_or_bounce_characters() {
+    let mut app = simulation_app(PlayMode::SideScroll);
+    spawn_wall(&mut app, 300.0, 0.0);
+    let walker = spawn_character(&mut app, 0.0, Vec2::X);
+    for _ in 0..300 {
+        app.update();
+    }
+    assert_eq!(position_along_ground(&app, walker), 300.0 - BODY_HALF_WIDTH);
+    let velocity = app
+        .world()
+        .get::<BasicMovementController>(walker)
+        .unwrap()
+        .velocity();
+    assert_eq!(velocity, 0.0);
+
+    // Coasting into a bouncy wall knocks the character back from it.
+    let mut app = simulation_app(PlayMode::SideScroll);
+    spawn_wall(&mut app, 30.0, 0.5);
+    let coaster = spawn_character(&mut app, 500.0, Vec2::ZERO);
+    for _ in 0..120 {
+        app.update();
+    }
+    let rest = position_along_ground(&app, coaster);
+    assert!(
+        rest < 30.0 - BODY_HALF_WIDTH - 5.0,
+        "came to rest at {rest}"
+    );
+}
+
+#[test]
+fn jumps_up_through_platforms_and_lands_on_them() {
+    let mut app = simulation_app(PlayMode::SideScroll);
+    let platform = app
+        .world_mut()
+        .spawn((
+            Platform { half_width: 100.0 },
+            Transform::from_xyz(0.0, 120.0, 0.0),
+        ))
+        .id();
+    let jumper = spawn_character(&mut app, 0.0, Vec2::Y);
+    app.update();
+    app.world_mut().get_mut::<MovementIntent>(jumper).unwrap().0 = Vec2::ZERO;
+
+    let mut highest: f32 = 0.0;
+    for _ in 0..120 {
+        app.update();
+        highest = highest.max(app.world().get::<Transform>(jumper).unwrap().translation.y);
+    }
+    assert!(highest > 120.0, "never rose through the platform");
+    assert_eq!(
+        app.world().get::<Footing>(jumper),
+        Some(&Footing::Platform(platform))
+    );
+    assert_eq!(
+        app.world().get::<Transform>(jumper).unwrap().translation.y,
+        120.0
+    );
+
+    // Walking off the end drops the character back to the ground.
+    app.world_mut().get_mut::<MovementIntent>(jumper).unwrap().0 = Vec2::X;
+    for _ in 0..120 {
+        app.update();
+    }
+    assert_eq!(app.world().get::<Footing>(jumper), Some(&Footing::Ground));
+    assert_eq!(
+        app.world().get::<Transform>(jumper).unwrap().translation.y,
+        0.0
+    );
+}
+
+#[test]
+fn hills_positions_invert_placement() {
+    // Later periods of the hills are steep, so check well beyond the first.
+    let profile = GroundProfile::default();
+    for step in -300..900 {
+        let x = 13.7 * step as f32;
+        let arc_pos = Ground::Hills.arc_position_at(&profile, x);
+        let (point, _) = Ground::Hills.placement(&profile, arc_pos);
+        assert!((point.x - x).abs() < 0.05, "{x} came back as {}", point.x);
+    }
+}
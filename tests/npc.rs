@@ -0,0 +1,101 @@
+//! NPC behaviours steer the top-down controller with only the simulation.
+
+mod common;
+
+use bevy::prelude::*;
+use char_motion_example::{
+    PlayMode,
+    npc::{Behaviour, Npc},
+    top_down::movement::MovementController,
+};
+
+use common::simulation_app;
+
+fn position(app: &App, entity: Entity) -> Vec2 {
+    app.world()
+        .get::<Transform>(entity)
+        .unwrap()
+        .translation
+        .xy()
+}
+
+#[test]
+fn seekers_close_in_and_fleers_get_away() {
+    let mut app = simulation_app(PlayMode::TopDown);
+    let target = app
+        .world_mut()
+        .spawn((MovementController::default(), Transform::default()))
+        .id();
+    let seeker = app
+        .world_mut()
+        .spawn((
+            Npc,
+            Behaviour::Seek {
+                target,
+                radius: 50.0,
+            },
+            MovementController::default(),
+            Transform::from_xyz(600.0, 0.0, 0.0),
+        ))
+        .id();
+    let fleer = app
+        .world_mut()
+        .spawn((
+            Npc,
+            Behaviour::Flee {
+                target,
+                radius: 300.0,
+            },
+            MovementController::default(),
+            Transform::from_xyz(0.0, 100.0, 0.0),
+        ))
+        .id();
+
+    for _ in 0..600 {
+        app.update();
+    }
+
+    assert!(
+        position(&app, seeker).length() < 150.0,
+        "{}",
+        position(&app, seeker)
+    );
+    assert!(
+        position(&app, fleer).length() > 300.0,
+        "{}",
+        position(&app, fleer)
+    );
+    assert_eq!(position(&app, target), Vec2::ZERO);
+}
+
+#[test]
+fn patrols_visit_every_waypoint() {
+    let mut app = simulation_app(PlayMode::TopDown);
+    let waypoints = vec![Vec2::new(200.0, 0.0), Vec2::new(200.0, 200.0)];
+    let patroller = app
+        .world_mut()
+        .spawn((
+            Npc,
+            Behaviour::Patrol {
+                waypoints,
+                next: 0,
+                radius: 20.0,
+            },
+            MovementController::default(),
+            Transform::default(),
+        ))
+        .id();
+
+    let mut visited = 0;
+    for _ in 0..1200 {
+        app.update();
+        let Behaviour::Patrol { next, .. } = app.world().get::<Behaviour>(patroller).unwrap()
+        else {
+            unreachable!();
+        };
+        if *next != visited % 2 {
+            visited += 1;
+        }
+    }
+    assert!(visited >= 2, "visited {visited} waypoints");
+}
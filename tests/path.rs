@@ -0,0 +1,116 @@
+//! Path followers reach their waypoints and come to rest on the last one.
+
+mod common;
+
+use bevy::prelude::*;
+use char_motion_example::{
+    MotionParameters, PlayMode,
+    top_down::{movement::MovementController, path::PathFollower, surface::Surface},
+};
+
+use common::simulation_app;
+
+fn follow(params: MotionParameters, waypoints: &[Vec2], frames: usize) -> (App, Entity, f32) {
+    let mut app = simulation_app(PlayMode::TopDown);
+    app.insert_resource(params);
+    let follower = app
+        .world_mut()
+        .spawn((
+            PathFollower::new(waypoints.iter().copied()),
+            MovementController::default(),
+            Transform::default(),
+        ))
+        .id();
+
+    let mut worst_error: f32 = 0.0;
+    for _ in 0..frames {
+        app.update();
+        let path = app.world().get::<PathFollower>(follower).unwrap();
+        worst_error = worst_error.max(path.tracking_error());
+    }
+    (app, follower, worst_error)
+}
+
+fn position(app: &App, entity: Entity) -> Vec2 {
+    app.world()
+        .get::<Transform>(entity)
+        .unwrap()
+        .translation
+        .xy()
+}
+
+#[test]
+fn brakes_to_a_stop_on_the_last_waypoint() {
+    for params in [
+        MotionParameters::full(500.0, 1.0, 1.0, 5.0),
+        MotionParameters::full(500.0, 0.5, 8.0, 8.0),
+        MotionParameters::full(800.0, 0.3, 0.5, 2.0),
+    ] {
+        let target = Vec2::new(1500.0, 0.0);
+        let (app, follower, _) = follow(params, &[target], 900);
+        let path = app.world().get::<PathFollower>(follower).unwrap();
+        assert!(path.is_finished(), "{params:?}");
+        let rest = position(&app, follower);
+        // Letting go happens on a frame boundary, which can cost a frame of travel.
+        let frame_of_travel = params.max_speed() / 60.0;
+        assert!(
+            rest.distance(target) <= frame_of_travel,
+            "{params:?} came to rest at {rest}"
+        );
+        let speed = app
+            .world()
+            .get::<MovementController>(follower)
+            .unwrap()
+            .speed();
+        assert!(speed < 1.0, "{params:?} still moving at {speed}");
+    }
+}
+
+#[test]
+fn turns_early_enough_to_stay_near_the_path() {
+    let waypoints = [
+        Vec2::new(600.0, 0.0),
+        Vec2::new(600.0, 600.0),
+        Vec2::new(0.0, 600.0),
+    ];
+    let (app, follower, worst_error) = follow(
+        MotionParameters::full_with_separate_turn(500.0, 0.5, 4.0, 4.0, 4.0),
+        &waypoints,
+        1200,
+    );
+    let path = app.world().get::<PathFollower>(follower).unwrap();
+    assert!(path.is_finished());
+    assert!(worst_error < 100.0, "strayed {worst_error} from the path");
+}
+
+#[test]
+fn brakes_for_the_ice_it_is_on() {
+    let params = MotionParameters::full(500.0, 0.5, 5.0, 5.0);
+    let target = Vec2::new(1500.0, 0.0);
+    let mut app = simulation_app(PlayMode::TopDown);
+    app.insert_resource(params);
+    app.world_mut().spawn(Surface {
+        half_size: Vec2::splat(5000.0),
+        grip: 0.2,
+    });
+    let follower = app
+        .world_mut()
+        .spawn((
+            PathFollower::new([target]),
+            MovementController::default(),
+            Transform::default(),
+        ))
+        .id();
+    for _ in 0..1200 {
+        app.update();
+    }
+
+    // Braking for the ordinary tuning would slide well past the target.
+    let path = app.world().get::<PathFollower>(follower).unwrap();
+    assert!(path.is_finished());
+    let rest = position(&app, follower);
+    assert!(
+        rest.distance(target) <= params.max_speed() / 60.0,
+        "came to rest at {rest}"
+    );
+}
@@ -0,0 +1,49 @@
+//! Predictions agree with the closed-form stopping distance.
+
+use bevy::math::Vec2;
+use char_motion_example::{
+    MotionParameters,
+    prediction::{predict_side_scroll, predict_top_down},
+};
+
+#[test]
+fn letting_go_stops_at_the_stopping_distance() {
+    let params = MotionParameters::full(500.0, 0.8, 3.0, 2.0);
+    let velocity = Vec2::new(300.0, -400.0);
+    let expected = params.stopping_distance(velocity.length());
+    for dt in [1.0 / 30.0, 1.0 / 60.0, 1.0 / 144.0] {
+        let steps = (30.0 / dt) as usize;
+        let top_down = predict_top_down(
+            Vec2::ZERO,
+            velocity,
+            std::iter::repeat_n(Vec2::ZERO, steps),
+            dt,
+            &params,
+        );
+        let stop = top_down.last().unwrap().position;
+        assert!((stop.length() - expected).abs() < 0.01, "{stop} at dt={dt}");
+        assert!((stop.normalize() - velocity.normalize()).length() < 1e-4);
+
+        let side_scroll = predict_side_scroll(
+            0.0,
+            -velocity.length(),
+            std::iter::repeat_n(0.0, steps),
+            |_| 0.0,
+            dt,
+            &params,
+        );
+        let stop = side_scroll.last().unwrap().position.x;
+        assert!((stop + expected).abs() < 0.01, "{stop} at dt={dt}");
+    }
+}
+
+#[test]
+fn predictions_follow_the_intents() {
+    let params = MotionParameters::basic(500.0, 1.0);
+    let intents = [Vec2::X; 60].into_iter().chain([Vec2::NEG_X; 60]);
+    let states = predict_top_down(Vec2::ZERO, Vec2::ZERO, intents, 1.0 / 60.0, &params);
+    assert_eq!(states.len(), 120);
+    assert!((states[119].time - 2.0).abs() < 1e-4);
+    assert!(states[59].velocity.x > 0.0);
+    assert!(states[119].velocity.x < states[59].velocity.x);
+}
@@ -0,0 +1,41 @@
+//! Cycling through presets follows their order, even when two share a tuning.
+
+use char_motion_example::{
+    MotionParameters,
+    presets::{Preset, Presets, SelectedPreset, preset_name},
+};
+
+fn presets() -> Presets {
+    let preset = |name: &str, max_speed: f32| Preset {
+        name: name.to_string(),
+        params: MotionParameters::basic(max_speed, 1.0),
+    };
+    Presets(vec![
+        preset("Slow", 250.0),
+        preset("Twin", 500.0),
+        preset("Twin Again", 500.0),
+    ])
+}
+
+#[test]
+fn stepping_visits_every_preset_in_turn() {
+    let presets = presets();
+    let mut selected = None;
+    let mut visited = Vec::new();
+    for _ in 0..4 {
+        selected = presets.step(selected, 1);
+        visited.push(preset_name(&presets, SelectedPreset(selected)));
+    }
+    assert_eq!(visited, ["Slow", "Twin", "Twin Again", "Slow"]);
+    assert_eq!(presets.step(Some(0), -1), Some(2));
+}
+
+#[test]
+fn a_custom_tuning_steps_from_either_end() {
+    let presets = presets();
+    assert_eq!(presets.step(None, 1), Some(0));
+    assert_eq!(presets.step(None, -1), Some(2));
+    assert_eq!(preset_name(&presets, SelectedPreset(None)), "Custom");
+    assert_eq!(presets.position("twin again"), Some(2));
+    assert_eq!(Presets::default().step(None, 1), None);
+}
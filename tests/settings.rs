@@ -0,0 +1,116 @@
+//! Saved settings give way to the launch's own choices, keep a tuning for
+//! each mode, and fill in whatever they don't mention with the defaults.
+
+use bevy::prelude::*;
+use char_motion_example::{
+    MotionParameters, PlayMode,
+    input::{Action, KeyBindings},
+    settings::{LaunchChoices, Settings},
+    side_scroll, top_down,
+};
+
+fn saved() -> Settings {
+    Settings {
+        side_scroll_params: Some(MotionParameters::full(300.0, 0.5, 2.0, 2.0)),
+        top_down_params: Some(MotionParameters::full(700.0, 1.0, 8.0, 8.0)),
+        side_scroll_ground: side_scroll::Ground::Hills,
+        top_down_ground: top_down::Ground::Rooms,
+        ..default()
+    }
+}
+
+/// A world holding what the app was launched with.
+fn launched() -> World {
+    let mut world = World::new();
+    world.insert_resource(MotionParameters::full(500.0, 0.5, 5.0, 5.0));
+    world.insert_resource(side_scroll::Ground::Platforms);
+    world.insert_resource(top_down::Ground::Tiles);
+    world
+}
+
+#[test]
+fn launch_choices_beat_saved_values() {
+    let mut world = launched();
+    let launch = LaunchChoices {
+        params: true,
+        side_scroll_ground: true,
+        top_down_ground: true,
+    };
+    saved().apply(&mut world, launch, PlayMode::SideScroll);
+    assert_eq!(
+        *world.resource::<MotionParameters>(),
+        MotionParameters::full(500.0, 0.5, 5.0, 5.0)
+    );
+    assert_eq!(
+        *world.resource::<side_scroll::Ground>(),
+        side_scroll::Ground::Platforms
+    );
+    assert_eq!(
+        *world.resource::<top_down::Ground>(),
+        top_down::Ground::Tiles
+    );
+
+    // Whatever the launch left open is restored.
+    let mut world = launched();
+    let launch = LaunchChoices {
+        side_scroll_ground: true,
+        ..default()
+    };
+    saved().apply(&mut world, launch, PlayMode::SideScroll);
+    assert_eq!(
+        *world.resource::<MotionParameters>(),
+        MotionParameters::full(300.0, 0.5, 2.0, 2.0)
+    );
+    assert_eq!(
+        *world.resource::<side_scroll::Ground>(),
+        side_scroll::Ground::Platforms
+    );
+    assert_eq!(
+        *world.resource::<top_down::Ground>(),
+        top_down::Ground::Rooms
+    );
+}
+
+#[test]
+fn the_saved_tuning_is_per_mode() {
+    for (mode, params) in [
+        (
+            PlayMode::SideScroll,
+            MotionParameters::full(300.0, 0.5, 2.0, 2.0),
+        ),
+        (
+            PlayMode::TopDown,
+            MotionParameters::full(700.0, 1.0, 8.0, 8.0),
+        ),
+    ] {
+        let mut world = launched();
+        saved().apply(&mut world, default(), mode);
+        assert_eq!(*world.resource::<MotionParameters>(), params, "{mode:?}");
+    }
+
+    // A mode with no saved tuning keeps the launch's.
+    let mut world = launched();
+    let settings = Settings {
+        top_down_params: None,
+        ..saved()
+    };
+    settings.apply(&mut world, default(), PlayMode::TopDown);
+    assert_eq!(
+        *world.resource::<MotionParameters>(),
+        MotionParameters::full(500.0, 0.5, 5.0, 5.0)
+    );
+}
+
+#[test]
+fn missing_key_bindings_keep_their_defaults() {
+    let settings: Settings = ron::from_str("(key_bindings: { Jump: [KeyW] })").unwrap();
+    let defaults = KeyBindings::default();
+    assert_eq!(settings.key_bindings.keys(Action::Jump), [KeyCode::KeyW]);
+    for action in [Action::MoveLeft, Action::Pause, Action::ToggleStopPoint] {
+        assert_eq!(
+            settings.key_bindings.keys(action),
+            defaults.keys(action),
+            "{action:?}"
+        );
+    }
+}
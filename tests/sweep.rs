@@ -0,0 +1,78 @@
+//! Sweep values parse from the command line, and the measured metrics agree
+//! with the closed forms the tuning predicts.
+
+use char_motion_example::{
+    MotionParameters,
+    sweep::{SweepController, SweepValues, side_scroll_metrics, sweep, to_csv, top_down_metrics},
+};
+
+#[test]
+fn sweep_values_parse_single_values_lists_and_ranges() {
+    assert_eq!("2.5".parse(), Ok(SweepValues(vec![2.5])));
+    assert_eq!("1, 2,4".parse(), Ok(SweepValues(vec![1.0, 2.0, 4.0])));
+    assert_eq!(
+        "0:1:5".parse(),
+        Ok(SweepValues(vec![0.0, 0.25, 0.5, 0.75, 1.0]))
+    );
+    assert_eq!("3:9:1".parse(), Ok(SweepValues(vec![3.0])));
+    assert_eq!("3:9:0".parse(), Ok(SweepValues(vec![])));
+
+    for bad in ["", "fast", "1,,2", "1:2", "1:2:3:4", "1:2:many", "1:2:-3"] {
+        assert!(bad.parse::<SweepValues>().is_err(), "{bad:?} parsed");
+    }
+}
+
+#[test]
+fn distances_match_the_closed_forms() {
+    for params in [
+        MotionParameters::full_with_separate_turn(500.0, 1.0, 5.0, 5.0, 2.0),
+        MotionParameters::full_with_separate_turn(300.0, 0.5, 2.0, 8.0, 4.0),
+    ] {
+        let speed = params.max_speed();
+        // The metrics stop counting at 1% of `max_speed`, missing the last 1%
+        // of the distance.
+        let close =
+            |measured: f32, predicted: f32| (0.97 * predicted..=predicted).contains(&measured);
+        for metrics in [side_scroll_metrics(&params), top_down_metrics(&params)] {
+            let stopping = metrics.stopping_distance.unwrap();
+            let predicted = params.stopping_distance(speed);
+            assert!(close(stopping, predicted), "{stopping} vs {predicted}");
+        }
+
+        let turn = top_down_metrics(&params).turn_radius.unwrap();
+        let predicted = params.turning_distance(speed);
+        assert!(close(turn, predicted), "{turn} vs {predicted}");
+        assert_eq!(side_scroll_metrics(&params).turn_radius, None);
+    }
+}
+
+#[test]
+fn times_grow_with_the_acceleration_time() {
+    let quick = top_down_metrics(&MotionParameters::full(500.0, 0.5, 5.0, 5.0));
+    let slow = top_down_metrics(&MotionParameters::full(500.0, 1.0, 5.0, 5.0));
+    assert!(quick.time_to_90.unwrap() < slow.time_to_90.unwrap());
+    assert!(quick.reversal_time.unwrap() < slow.reversal_time.unwrap());
+
+    // A controller that never damps its way to a stop leaves the metric blank.
+    let sliding = top_down_metrics(&MotionParameters::full(500.0, 1.0, 0.0, 5.0));
+    assert_eq!(sliding.stopping_distance, None);
+}
+
+#[test]
+fn every_combination_gets_a_row_per_controller() {
+    let rows = sweep(
+        &SweepValues(vec![300.0, 500.0]),
+        &SweepValues(vec![1.0]),
+        &SweepValues(vec![2.0, 5.0]),
+        &SweepValues(vec![5.0]),
+        &SweepValues(vec![5.0]),
+    );
+    assert_eq!(rows.len(), 2 * 2 * 2);
+    assert_eq!(rows[0].0, SweepController::SideScroll);
+    assert_eq!(rows[1].0, SweepController::TopDown);
+
+    let csv = to_csv(&rows);
+    assert_eq!(csv.lines().count(), 1 + rows.len());
+    // Side-scroll rows have no turn radius.
+    assert!(csv.lines().nth(1).unwrap().ends_with(','));
+}
@@ -0,0 +1,113 @@
+//! Level files parse, and their tiles become the ground, walls, platforms,
+//! obstacles and surfaces they stand for.
+
+use bevy::prelude::*;
+use char_motion_example::{
+    tilemap::{Tile, Tilemap, UnknownTile},
+    top_down::collision::Obstacle,
+};
+
+/// A map of 10 pixel tiles from rows given one per line.
+fn tilemap(rows: &str) -> Tilemap {
+    Tilemap {
+        tile_size: 10.0,
+        rows: rows.lines().map(str::to_string).collect(),
+    }
+}
+
+#[test]
+fn shipped_levels_parse() {
+    for path in ["side_scroll.level.ron", "top_down.level.ron"] {
+        let text = std::fs::read_to_string(format!("assets/levels/{path}")).unwrap();
+        let tilemap: Tilemap = ron::from_str(&text).unwrap();
+        assert_eq!(tilemap.validate(), Ok(()), "{path}");
+        assert_eq!(tilemap.spawn_points().len(), 1, "{path}");
+    }
+}
+
+#[test]
+fn unknown_tiles_are_reported() {
+    let tilemap = tilemap("....\n..x.\n####");
+    assert_eq!(
+        tilemap.validate(),
+        Err(UnknownTile {
+            row: 1,
+            column: 2,
+            tile: 'x'
+        })
+    );
+}
+
+#[test]
+fn side_scroll_tiles_build_ground_walls_and_platforms() {
+    let tilemap = tilemap(
+        "......
+.==..|
+P....|
+...#.|
+######",
+    );
+    let profile = tilemap.ground_profile();
+    let heights: Vec<_> = profile.points().iter().map(|point| point.y).collect();
+    assert_eq!(heights, [10.0, 10.0, 10.0, 20.0, 10.0, 10.0]);
+    // The ground is flat up to the first slope, so positions along it match `x`.
+    assert_eq!(profile.arc_position_at(12.0), 12.0);
+    let (point, angle) = profile.placement(profile.arc_position_at(30.0));
+    assert!(point.distance(Vec2::new(30.0, 15.0)) < 1e-3, "{point}");
+    assert!((angle - std::f32::consts::FRAC_PI_4).abs() < 1e-3);
+
+    let platforms = tilemap.platforms();
+    assert_eq!(platforms.len(), 1);
+    assert_eq!(platforms[0].0, Vec2::new(20.0, 40.0));
+    assert_eq!(platforms[0].1.half_width, 10.0);
+
+    let walls = tilemap.walls(&profile);
+    assert_eq!(walls.len(), 1);
+    assert_eq!(walls[0].1.height, 30.0);
+    assert_eq!(profile.placement(walls[0].0).0, Vec2::new(55.0, 10.0));
+
+    assert_eq!(tilemap.spawn_points(), [Vec2::new(5.0, 25.0)]);
+}
+
+#[test]
+fn top_down_tiles_build_obstacles_and_surfaces() {
+    let tilemap = tilemap(
+        "####
+o~~.
+..P.",
+    );
+    let obstacles = tilemap.obstacles();
+    assert_eq!(
+        obstacles,
+        [
+            (
+                Vec2::new(20.0, 25.0),
+                Obstacle::Aabb(Rectangle::new(40.0, 10.0))
+            ),
+            (Vec2::new(5.0, 15.0), Obstacle::Circle(Circle::new(5.0))),
+        ]
+    );
+    let surfaces = tilemap.surfaces();
+    assert_eq!(surfaces.len(), 1);
+    assert_eq!(surfaces[0].0, Vec2::new(20.0, 15.0));
+    assert_eq!(surfaces[0].1.half_size, Vec2::new(10.0, 5.0));
+    assert!(surfaces[0].1.grip < 1.0);
+}
+
+#[test]
+fn edits_survive_saving() {
+    let mut tilemap = tilemap(
+        "....
+....
+##..",
+    );
+    assert_eq!(tilemap.cell_at(Vec2::new(25.0, 15.0)), Some((2, 1)));
+    assert_eq!(tilemap.cell_at(Vec2::new(-5.0, 15.0)), None);
+    tilemap.set_tile(2, 1, Tile::Round);
+    tilemap.set_surface(3, 0);
+    tilemap.set_surface(0, 2);
+    assert_eq!(tilemap.rows, ["...#", "..o#", "##.#"]);
+
+    let saved = ron::ser::to_string_pretty(&tilemap, default()).unwrap();
+    assert_eq!(ron::from_str::<Tilemap>(&saved).unwrap(), tilemap);
+}